@@ -1,10 +1,21 @@
-use std::{fmt::Display, fs};
+#![macro_use]
+
+use anyhow::{anyhow, Result};
+use guard::{guarded, Guarded};
+use inputs::Source;
+use report::{Format, PartResult, Reporter};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use std::{collections::HashMap, fs};
+
+pub use runner::{guard, inputs, log, report};
 
 // the return type for parts sometime its Numbers sometimes its Strings
 #[derive(Debug, PartialEq, Eq)]
-#[allow(dead_code)]
 pub enum Answer {
     Num(i128),
+    #[allow(dead_code)]
     Str(String),
 }
 
@@ -17,8 +28,139 @@ impl Display for Answer {
     }
 }
 
-// small utility to use for reading the input and test files in tests
-pub fn read_from_file(filename: &str) -> String {
-    println!("reading {}", filename);
-    fs::read_to_string(filename).unwrap_or_else(|msg| panic!("error reading {}: {}", filename, msg))
+/// Root of this project, relative `--input` paths start here and not in the working directory.
+pub fn project_dir() -> PathBuf {
+    inputs::canonical(Path::new(env!("CARGO_MANIFEST_DIR")).to_owned())
+}
+
+pub const YEAR: u16 = 2022;
+
+type Solution = fn(&str) -> Result<Answer>;
+
+pub struct Solver {
+    solutions: HashMap<(u8, u8), Solution>,
+    /// wall-clock limit per part, `None` waits forever
+    pub timeout: Option<Duration>,
+}
+
+impl Solver {
+    pub fn new() -> Solver {
+        Solver {
+            solutions: HashMap::new(),
+            timeout: None,
+        }
+    }
+    pub fn add(&mut self, day: u8, part: u8, fun: Solution) {
+        self.solutions.insert((day, part), fun);
+    }
+    /// returns false if any of the selected parts failed or gave a wrong answer
+    pub fn solve(&self, opt: crate::Opt, source: &Source, format: Format) -> bool {
+        let mut reporter = Reporter::for_days(format);
+        match opt {
+            crate::Opt::All => self.solve_all(source, &mut reporter),
+            crate::Opt::Day { day, part } => self.solve_day(day, part, source, &mut reporter),
+            crate::Opt::File { day, part, .. } => {
+                self.solve_day(day, Some(part), source, &mut reporter)
+            }
+        }
+        reporter.finish()
+    }
+    fn solve_all(&self, source: &Source, reporter: &mut Reporter) {
+        for i in 1..=25 {
+            self.solve_day(i, None, source, reporter);
+        }
+    }
+    fn solve_day(&self, day: u8, part: Option<u8>, source: &Source, reporter: &mut Reporter) {
+        let parts = match part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        };
+        let content = source.read(YEAR, day);
+        for part in parts {
+            let result = self
+                .solve_day_part(day, part, &content)
+                .with_input(source.origin(YEAR, day));
+            // the known answers only hold for the puzzle input
+            let expected = match source {
+                Source::Store(variant) if variant == "input" => known_answer(day, part),
+                _ => None,
+            };
+            reporter.report(result.with_expected(expected));
+        }
+    }
+    fn solve_day_part(&self, day: u8, part: u8, content: &Result<String>) -> PartResult {
+        let result = PartResult::new(YEAR, day, part);
+        match content {
+            Ok(content) => self.solve_day_part_content(result, content),
+            Err(err) => result.with_outcome::<Answer>(&Err(anyhow!("{:#}", err))),
+        }
+    }
+    fn solve_day_part_content(&self, result: PartResult, content: &str) -> PartResult {
+        let Some(fun) = self.solutions.get(&(result.day, result.part)) else {
+            return result
+                .with_outcome::<Answer>(&Err(anyhow!("No solution was added to solver!")));
+        };
+        let fun = *fun;
+        let content = content.to_owned();
+        let outcome = guarded(self.timeout, move || {
+            let start = Instant::now();
+            let answer = fun(&content);
+            (answer, start.elapsed().as_nanos())
+        });
+        match outcome {
+            Guarded::Done((answer, solve_ns)) => {
+                let mut result = result.with_outcome(&answer);
+                // parsing happens inside the parts, so it is included in solve_ns
+                result.solve_ns = Some(solve_ns);
+                result
+            }
+            Guarded::Panicked(msg) => result.with_panic(&msg),
+            Guarded::TimedOut => result.with_timeout(self.timeout.unwrap()),
+        }
+    }
+}
+
+/// the accepted answer for the puzzle input of a day, `src/dayNN/answers.txt`
+/// has one `partN: <answer>` line per part
+fn known_answer(day: u8, part: u8) -> Option<String> {
+    let path = project_dir().join(format!("src/day{:02}/answers.txt", day));
+    let answers = fs::read_to_string(path).ok()?;
+    let prefix = format!("part{}:", part);
+    answers
+        .lines()
+        .find_map(|l| l.strip_prefix(&prefix))
+        .map(|a| a.trim().to_owned())
+}
+
+macro_rules! regex {
+    ($re:literal) => {{
+        static RE: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::new(|| {
+            // println!("initializing regex {}", $re);
+            regex::Regex::new($re).unwrap()
+        });
+        &RE
+    }};
+}
+
+#[cfg(test)]
+#[macro_use]
+pub mod test_utils {
+    pub fn read_from_store(day: &str, variant: &str) -> String {
+        super::inputs::read_for_test(super::YEAR, day, variant)
+    }
+    macro_rules! extract_day_from_path {
+        () => {{
+            let path = module_path!();
+            let re = regex!(r"day\d{2}");
+            let m = re
+                .find(path)
+                .expect("macro is only valid inside paths containing 'day\\d{2}' pattern");
+            m.as_str()
+        }};
+    }
+    macro_rules! local_file {
+        ($file:literal) => {
+            LazyLock::new(|| test_utils::read_from_store(extract_day_from_path!(), $file))
+        };
+    }
 }
@@ -1,12 +1,5 @@
 use super::common::*;
 use anyhow::{anyhow, Result};
-use lazy_static::lazy_static;
-use regex::Regex;
-
-lazy_static! {
-    pub static ref TEST: String = read_from_file("src/day01/test.txt");
-    pub static ref INPUT: String = read_from_file("src/day01/input.txt");
-}
 
 struct Elves {
     elf_vec: Vec<Elf>,
@@ -24,10 +17,7 @@ impl Elf {
 
 fn parse_input(buffer: &str) -> Result<Elves> {
     let mut elf_vec: Vec<Elf> = Vec::new();
-    lazy_static! {
-        static ref RE: Regex = Regex::new(r"(\n|\r\n){2}").unwrap();
-    }
-    let all_elf_calories = RE.split(buffer).collect::<Vec<_>>();
+    let all_elf_calories = regex!(r"(\n|\r\n){2}").split(buffer).collect::<Vec<_>>();
 
     for elf_calories in all_elf_calories {
         let calories = elf_calories
@@ -66,12 +56,11 @@ pub fn part_two(input: &str) -> Result<Answer> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::common::read_from_file;
+    use std::sync::LazyLock;
+    use test::Bencher;
 
-    // lazy_static! {
-    //     static ref TEST: String = read_from_file("test.txt");
-    //     static ref INPUT: String = read_from_file("input.txt");
-    // }
+    static TEST: LazyLock<String> = local_file!("test");
+    static INPUT: LazyLock<String> = local_file!("input");
 
     #[test]
     fn test_one() -> Result<()> {
@@ -79,31 +68,31 @@ mod tests {
         assert_eq!(answer, Answer::Num(24000));
         Ok(())
     }
-
-    // Use "cargo test --release -- part_one --nocapture" to print the time
     #[test]
     fn part_one() -> Result<()> {
-        let t = std::time::Instant::now();
         let answer = super::part_one(&INPUT)?;
-        eprintln!("Part one took {:0.2?}", t.elapsed());
         assert_eq!(answer, Answer::Num(66306));
         Ok(())
     }
-
     #[test]
     fn test_two() -> Result<()> {
         let answer = super::part_two(&TEST)?;
         assert_eq!(answer, Answer::Num(45000));
         Ok(())
     }
-
-    // Use "cargo test --release -- part_two --nocapture" to print the time
     #[test]
     fn part_two() -> Result<()> {
-        let t = std::time::Instant::now();
         let answer = super::part_two(&INPUT)?;
-        eprintln!("Part two took {:0.2?}", t.elapsed());
         assert_eq!(answer, Answer::Num(195292));
         Ok(())
     }
+
+    #[bench]
+    fn bench_part_one(b: &mut Bencher) {
+        b.iter(part_one)
+    }
+    #[bench]
+    fn bench_part_two(b: &mut Bencher) {
+        b.iter(part_two)
+    }
 }
@@ -1,84 +1,91 @@
-use anyhow::Result;
-use common::Answer;
-use std::{collections::HashMap, fs, path::PathBuf};
-use structopt::StructOpt;
+#![feature(test)]
+extern crate test;
+#[macro_use]
+extern crate runner;
 
 mod common;
 mod day01;
 mod day02;
 
+use anyhow::{bail, Result};
+use common::*;
+use std::{path::PathBuf, time::Duration};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+struct Cli {
+    /// -v shows debug output, -vv also trace output (on stderr)
+    #[structopt(short, long, parse(from_occurrences))]
+    verbose: u64,
+    /// only show diagnostic output of these days, e.g. day09,day12
+    #[structopt(long)]
+    log_day: Option<String>,
+    /// write diagnostic output to this file instead of stderr
+    #[structopt(long, parse(from_os_str))]
+    log_file: Option<PathBuf>,
+    /// text, json (one object per part), csv or table
+    #[structopt(long, default_value = "text")]
+    format: report::Format,
+    /// seconds each part may take before it is reported as TIMEOUT, 0 waits forever
+    #[structopt(long, default_value = "60")]
+    timeout: f64,
+    /// read the input from this file instead of the store, `-` is stdin,
+    /// relative paths start at the project root
+    #[structopt(long, conflicts_with = "variant")]
+    input: Option<String>,
+    /// take this variant from the input store, e.g. test or test2
+    #[structopt(long)]
+    variant: Option<String>,
+    #[structopt(subcommand)]
+    opt: Opt,
+}
+
 #[derive(Debug, StructOpt)]
 enum Opt {
     All,
-    Day { day: u8, part: Option<u8> },
-    File { day: u8, part: u8, file: PathBuf },
-    // Stdin { day: u8, part: u8 },
+    Day {
+        day: u8,
+        part: Option<u8>,
+    },
+    /// deprecated, `--input <file> day <day> <part>` replaces it; relative paths start at the
+    /// current directory here, as they always did
+    File {
+        day: u8,
+        part: u8,
+        file: PathBuf,
+    },
 }
 
-fn main() {
-    let opt = Opt::from_args();
-    println!("{:?}", opt);
+fn main() -> Result<()> {
+    let cli = Cli::from_args();
+    log::init(
+        log::Level::from_verbosity(cli.verbose),
+        cli.log_day.as_deref(),
+        cli.log_file.as_deref(),
+    )?;
+    let opt = cli.opt;
+    let source = match (cli.input, cli.variant, &opt) {
+        (Some(path), _, Opt::All) => bail!("--input {} only works with a single day", path),
+        (None, None, Opt::File { file, .. }) => {
+            eprintln!(
+                "`file <day> <part> <file>` is deprecated, use `--input <file> day <day> <part>`"
+            );
+            inputs::Source::File(file.clone())
+        }
+        (_, _, Opt::File { .. }) => bail!("`file` already names the input, drop --input/--variant"),
+        (Some(path), _, _) => inputs::Source::from_arg(&path, &project_dir()),
+        (None, Some(variant), _) => inputs::Source::Store(variant),
+        (None, None, _) => inputs::Source::default(),
+    };
 
     let mut solver = Solver::new();
+    solver.timeout = (cli.timeout > 0.0).then(|| Duration::from_secs_f64(cli.timeout));
     solver.add(1, 1, day01::part_one);
     solver.add(1, 2, day01::part_two);
-    solver.solve(opt);
-}
-
-struct Solver {
-    solutions: HashMap<(u8, u8), fn(&str) -> Result<Answer>>,
-}
-
-impl Solver {
-    fn new() -> Solver {
-        Solver {
-            solutions: HashMap::new(),
-        }
-    }
-    fn add(&mut self, day: u8, part: u8, fun: fn(&str) -> Result<Answer>) {
-        self.solutions.insert((day, part), fun);
-    }
-    fn solve(&self, opt: Opt) {
-        match opt {
-            Opt::All => self.solve_all(),
-            Opt::Day { day, part } => self.solve_day(day, part),
-            Opt::File { day, part, file } => self.solve_day_part_file(day, part, file),
-        }
-    }
-    fn solve_all(&self) {
-        for i in 1..=25 {
-            self.solve_day(i, None);
-        }
-    }
-    fn solve_day(&self, day: u8, part: Option<u8>) {
-        let file = format!("src/day{:02}/input.txt", day);
-        match fs::read_to_string(&file) {
-            Ok(content) => {
-                if let Some(part) = part {
-                    self.solve_day_part_content(day, part, &content)
-                } else {
-                    self.solve_day_part_content(day, 1, &content);
-                    self.solve_day_part_content(day, 2, &content);
-                }
-            }
-            Err(err) => println!("Error reading {}: {}", file, err),
-        }
-    }
-    fn solve_day_part_file(&self, day: u8, part: u8, file: PathBuf) {
-        match fs::read_to_string(&file) {
-            Ok(content) => self.solve_day_part_content(day, part, &content),
-            Err(err) => println!("Error reading {}: {}", file.display(), err),
-        }
-    }
-    fn solve_day_part_content(&self, day: u8, part: u8, content: &str) {
-        print!("day{:02} part{:02}: ", day, part);
-        if let Some(fun) = self.solutions.get(&(day, part)) {
-            match fun(content) {
-                Ok(answer) => println!("{}", answer),
-                Err(err) => println!("Err: implementation failed with: {}", err),
-            }
-        } else {
-            println!("Err: No solution was added to solver!");
-        }
+    solver.add(2, 1, day02::part_one);
+    solver.add(2, 2, day02::part_two);
+    if !solver.solve(opt, &source, cli.format) {
+        std::process::exit(1);
     }
+    Ok(())
 }
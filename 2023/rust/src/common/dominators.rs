@@ -0,0 +1,146 @@
+/// Dominator tree of a directed graph given as successor lists, computed with the
+/// iterative algorithm of Cooper, Harvey and Kennedy ("A Simple, Fast Dominance Algorithm").
+/// `a` dominates `b` if every path from the root to `b` goes through `a`.
+pub struct Dominators {
+    root: usize,
+    /// immediate dominator, the root is its own, unreachable vertices have none
+    idom: Vec<Option<usize>>,
+    /// reachable vertices in postorder of the dfs from the root
+    postorder: Vec<usize>,
+}
+
+/// reachable vertices in postorder, iterative so that long chains don't overflow the stack
+fn postorder(root: usize, successors: &[Vec<usize>]) -> Vec<usize> {
+    let mut visited = vec![false; successors.len()];
+    let mut order = Vec::with_capacity(successors.len());
+    let mut stack = vec![(root, 0)];
+    visited[root] = true;
+    while let Some((v, next)) = stack.pop() {
+        match successors[v].get(next) {
+            Some(&w) => {
+                stack.push((v, next + 1));
+                if !visited[w] {
+                    visited[w] = true;
+                    stack.push((w, 0));
+                }
+            }
+            None => order.push(v),
+        }
+    }
+    order
+}
+
+impl Dominators {
+    pub fn new(root: usize, successors: &[Vec<usize>]) -> Dominators {
+        let n = successors.len();
+        let postorder = postorder(root, successors);
+        let mut number = vec![usize::MAX; n];
+        for (i, &v) in postorder.iter().enumerate() {
+            number[v] = i;
+        }
+        let mut predecessors = vec![Vec::new(); n];
+        for (v, succ) in successors.iter().enumerate() {
+            for &w in succ.iter() {
+                predecessors[w].push(v);
+            }
+        }
+
+        let mut idom = vec![None; n];
+        idom[root] = Some(root);
+        let intersect = |idom: &[Option<usize>], mut a: usize, mut b: usize| {
+            while a != b {
+                while number[a] < number[b] {
+                    a = idom[a].unwrap();
+                }
+                while number[b] < number[a] {
+                    b = idom[b].unwrap();
+                }
+            }
+            a
+        };
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &v in postorder.iter().rev().filter(|&&v| v != root) {
+                let mut processed = predecessors[v].iter().filter(|&&p| idom[p].is_some());
+                let Some(&first) = processed.next() else {
+                    continue;
+                };
+                let new_idom = processed.fold(first, |acc, &p| intersect(&idom, p, acc));
+                if idom[v] != Some(new_idom) {
+                    idom[v] = Some(new_idom);
+                    changed = true;
+                }
+            }
+        }
+        Dominators {
+            root,
+            idom,
+            postorder,
+        }
+    }
+
+    /// immediate dominator, `None` for the root and unreachable vertices
+    pub fn idom(&self, v: usize) -> Option<usize> {
+        self.idom[v].filter(|_| v != self.root)
+    }
+
+    /// number of vertices each vertex dominates, itself included, 0 if unreachable
+    pub fn subtree_sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.idom.len()];
+        // dominators come before the vertices they dominate in reverse postorder
+        for &v in self.postorder.iter() {
+            sizes[v] += 1;
+            if let Some(parent) = self.idom(v) {
+                sizes[parent] += sizes[v];
+            }
+        }
+        sizes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cooper_harvey_kennedy_example() {
+        // figure 4 of the paper with 6 as the root, renumbered to 0..=5 (6 -> 0, 5 -> 1, 4 -> 2,
+        // 1 -> 3, 2 -> 4, 3 -> 5), all vertices end up immediately dominated by the root
+        let successors = vec![
+            vec![1, 2],
+            vec![3],
+            vec![4, 5],
+            vec![4],
+            vec![3, 5],
+            vec![4],
+        ];
+        let dom = Dominators::new(0, &successors);
+        for v in 1..6 {
+            assert_eq!(dom.idom(v), Some(0));
+        }
+        assert_eq!(dom.idom(0), None);
+        assert_eq!(dom.subtree_sizes()[0], 6);
+    }
+
+    #[test]
+    fn diamond_with_tail() {
+        // 0 -> 1 -> {2, 3} -> 4 -> 5, 6 is unreachable
+        let successors = vec![
+            vec![1],
+            vec![2, 3],
+            vec![4],
+            vec![4],
+            vec![5],
+            vec![],
+            vec![0],
+        ];
+        let dom = Dominators::new(0, &successors);
+        assert_eq!(dom.idom(4), Some(1));
+        assert_eq!(dom.idom(5), Some(4));
+        assert_eq!(dom.idom(6), None);
+        assert_eq!(dom.subtree_sizes(), vec![6, 5, 1, 1, 2, 1, 0]);
+        assert_eq!(dom.idom(2), Some(1));
+        assert_eq!(dom.idom(3), Some(1));
+    }
+}
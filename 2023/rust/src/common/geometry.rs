@@ -0,0 +1,140 @@
+use super::grid::Dir;
+use anyhow::{anyhow, Result};
+use num::integer::gcd;
+
+/// (x, y) lattice point, y grows downwards like the rows of a grid
+pub type Point = (i128, i128);
+
+/// parses a run-length move like `R 6`, anything after the amount is ignored
+pub fn parse_move(line: &str) -> Result<(Dir, i128)> {
+    let mut split = line.split_whitespace();
+    let dir = split
+        .next()
+        .and_then(|d| d.chars().next())
+        .and_then(Dir::from_letter)
+        .ok_or_else(|| anyhow!("no direction in move {:?}", line))?;
+    let amount = split
+        .next()
+        .ok_or_else(|| anyhow!("no amount in move {:?}", line))?
+        .parse()?;
+    Ok((dir, amount))
+}
+
+/// Vertices of the polygon traced by the moves starting at the origin.
+/// The moves are expected to end at the origin again, which is not repeated.
+pub fn polygon_from_moves(moves: impl IntoIterator<Item = (Dir, i128)>) -> Vec<Point> {
+    let mut pos = (0, 0);
+    let mut polygon = vec![pos];
+    for (dir, amount) in moves {
+        let (dx, dy) = dir.delta();
+        pos = (pos.0 + dx * amount, pos.1 + dy * amount);
+        polygon.push(pos);
+    }
+    if polygon.len() > 1 && polygon.last() == polygon.first() {
+        polygon.pop();
+    }
+    polygon
+}
+
+fn edges(polygon: &[Point]) -> impl Iterator<Item = (Point, Point)> + '_ {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+/// shoelace formula, positive for clockwise polygons (y grows downwards)
+pub fn double_signed_area(polygon: &[Point]) -> i128 {
+    edges(polygon).map(|(a, b)| a.0 * b.1 - b.0 * a.1).sum()
+}
+
+/// twice the area, this is always an integer for lattice polygons
+pub fn double_area(polygon: &[Point]) -> i128 {
+    double_signed_area(polygon).abs()
+}
+
+/// number of lattice points on the edges of the polygon
+pub fn boundary_points(polygon: &[Point]) -> i128 {
+    edges(polygon)
+        .map(|(a, b)| gcd((b.0 - a.0).abs(), (b.1 - a.1).abs()))
+        .sum()
+}
+
+/// Pick's theorem: A = i + b/2 - 1
+pub fn interior_points(polygon: &[Point]) -> i128 {
+    (double_area(polygon) - boundary_points(polygon) + 2) / 2
+}
+
+/// all lattice points inside or on the polygon, e.g. the tiles of a dug out lagoon
+pub fn covered_points(polygon: &[Point]) -> i128 {
+    interior_points(polygon) + boundary_points(polygon)
+}
+
+pub fn on_boundary(polygon: &[Point], p: Point) -> bool {
+    edges(polygon).any(|(a, b)| {
+        cross(a, b, p) == 0
+            && a.0.min(b.0) <= p.0
+            && p.0 <= a.0.max(b.0)
+            && a.1.min(b.1) <= p.1
+            && p.1 <= a.1.max(b.1)
+    })
+}
+
+/// > 0 if p is left of the line a -> b (in y up orientation)
+fn cross(a: Point, b: Point, p: Point) -> i128 {
+    (b.0 - a.0) * (p.1 - a.1) - (p.0 - a.0) * (b.1 - a.1)
+}
+
+/// How often the polygon winds around p, 0 means outside.
+/// Points on the boundary give an unspecified result, check `on_boundary` first.
+pub fn winding_number(polygon: &[Point], p: Point) -> i32 {
+    let mut winding = 0;
+    for (a, b) in edges(polygon) {
+        if a.1 <= p.1 {
+            if b.1 > p.1 && cross(a, b, p) > 0 {
+                winding += 1;
+            }
+        } else if b.1 <= p.1 && cross(a, b, p) < 0 {
+            winding -= 1;
+        }
+    }
+    winding
+}
+
+/// strictly inside, points on the boundary are not contained
+pub fn contains(polygon: &[Point], p: Point) -> bool {
+    !on_boundary(polygon, p) && winding_number(polygon, p) != 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn square_from_moves() -> Result<()> {
+        let moves = ["R 2", "D 2", "L 2", "U 2"].map(parse_move);
+        let square = polygon_from_moves(moves.into_iter().collect::<Result<Vec<_>>>()?);
+        assert_eq!(square, vec![(0, 0), (2, 0), (2, 2), (0, 2)]);
+        assert_eq!(double_signed_area(&square), 8);
+        assert_eq!(boundary_points(&square), 8);
+        assert_eq!(interior_points(&square), 1);
+        assert_eq!(covered_points(&square), 9);
+        assert!(contains(&square, (1, 1)));
+        assert!(!contains(&square, (2, 1)));
+        assert!(on_boundary(&square, (2, 1)));
+        assert!(!contains(&square, (3, 1)));
+        Ok(())
+    }
+
+    #[test]
+    fn winding_matches_pick() {
+        // an L shaped polygon
+        let poly = vec![(0, 0), (4, 0), (4, 2), (2, 2), (2, 4), (0, 4)];
+        let inside = (-1..=5)
+            .flat_map(|x| (-1..=5).map(move |y| (x, y)))
+            .filter(|&p| contains(&poly, p))
+            .count();
+        assert_eq!(inside as i128, interior_points(&poly));
+        assert_eq!(winding_number(&poly, (1, 1)).abs(), 1);
+    }
+}
@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+
+/// (row, col) position inside a grid
+pub type Pos = (usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir {
+    pub const ALL: [Dir; 4] = [Dir::Up, Dir::Right, Dir::Down, Dir::Left];
+
+    /// maps the slope tiles `^>v<` to the only direction they can be left in
+    pub fn from_slope(c: char) -> Option<Dir> {
+        match c {
+            '^' => Some(Dir::Up),
+            '>' => Some(Dir::Right),
+            'v' => Some(Dir::Down),
+            '<' => Some(Dir::Left),
+            _ => None,
+        }
+    }
+    /// maps the move letters `URDL` of a dig plan or similar to directions
+    pub fn from_letter(c: char) -> Option<Dir> {
+        match c {
+            'U' => Some(Dir::Up),
+            'R' => Some(Dir::Right),
+            'D' => Some(Dir::Down),
+            'L' => Some(Dir::Left),
+            _ => None,
+        }
+    }
+    /// (dx, dy) of one step as a `geometry::Point`, y grows downwards like the rows
+    pub fn delta(self) -> (i128, i128) {
+        match self {
+            Dir::Up => (0, -1),
+            Dir::Right => (1, 0),
+            Dir::Down => (0, 1),
+            Dir::Left => (-1, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    pub rows: usize,
+    pub cols: usize,
+    cells: Vec<Vec<T>>,
+}
+
+impl<T> Grid<T> {
+    /// all rows are expected to have the same length
+    pub fn from_rows(cells: Vec<Vec<T>>) -> Grid<T> {
+        let rows = cells.len();
+        let cols = cells.first().map_or(0, |r| r.len());
+        Grid { rows, cols, cells }
+    }
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get(pos.0).and_then(|r| r.get(pos.1))
+    }
+    /// the neighbor of pos in direction dir if it is still inside the grid
+    pub fn step(&self, pos: Pos, dir: Dir) -> Option<Pos> {
+        let (r, c) = pos;
+        match dir {
+            Dir::Up if r > 0 => Some((r - 1, c)),
+            Dir::Right if c + 1 < self.cols => Some((r, c + 1)),
+            Dir::Down if r + 1 < self.rows => Some((r + 1, c)),
+            Dir::Left if c > 0 => Some((r, c - 1)),
+            _ => None,
+        }
+    }
+    /// 4 neighborhood of pos together with the direction leading there
+    pub fn neighbors(&self, pos: Pos) -> impl Iterator<Item = (Dir, Pos)> + '_ {
+        Dir::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d).map(|n| (d, n)))
+    }
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |r| (0..cols).map(move |c| (r, c)))
+    }
+}
+
+impl Grid<char> {
+    pub fn parse(input: &str) -> Grid<char> {
+        Grid::from_rows(input.lines().map(|l| l.chars().collect()).collect())
+    }
+}
+
+/// A corridor between two junctions, `path` contains both end points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    pub from: usize,
+    pub to: usize,
+    pub steps: usize,
+    pub path: Vec<Pos>,
+}
+
+/// Grid with all corridor cells contracted into weighted edges.
+/// Nodes are the requested terminals plus every passable cell with more than
+/// two passable neighbors, edges are directed.
+#[derive(Debug, Clone)]
+pub struct JunctionGraph {
+    pub nodes: Vec<Pos>,
+    pub edges: Vec<Edge>,
+    index: HashMap<Pos, usize>,
+    outgoing: Vec<Vec<usize>>,
+}
+
+impl JunctionGraph {
+    pub fn node_index(&self, pos: Pos) -> Option<usize> {
+        self.index.get(&pos).copied()
+    }
+    pub fn outgoing(&self, node: usize) -> impl Iterator<Item = &Edge> {
+        self.outgoing[node].iter().map(|&e| &self.edges[e])
+    }
+}
+
+/// Contracts all degree 2 cells of the grid, corridors can be walked in both directions.
+pub fn junction_graph<T>(
+    grid: &Grid<T>,
+    terminals: &[Pos],
+    passable: impl Fn(&T) -> bool,
+) -> JunctionGraph {
+    directed_junction_graph(grid, terminals, passable, |_| None)
+}
+
+/// Like `junction_graph`, but a cell for which `one_way` returns a direction
+/// can only be left in that direction (e.g. the `^>v<` slopes of 2023 day23).
+pub fn directed_junction_graph<T>(
+    grid: &Grid<T>,
+    terminals: &[Pos],
+    passable: impl Fn(&T) -> bool,
+    one_way: impl Fn(&T) -> Option<Dir>,
+) -> JunctionGraph {
+    let open = |pos: Pos| grid.get(pos).is_some_and(&passable);
+    let can_leave = |pos: Pos, dir: Dir| match grid.get(pos).and_then(&one_way) {
+        Some(only) => only == dir,
+        None => true,
+    };
+
+    let mut nodes: Vec<Pos> = terminals.to_vec();
+    for pos in grid.positions() {
+        if open(pos) && grid.neighbors(pos).filter(|&(_, n)| open(n)).count() > 2 {
+            nodes.push(pos);
+        }
+    }
+    let mut index = HashMap::new();
+    nodes.retain(|&pos| {
+        let next = index.len();
+        *index.entry(pos).or_insert(next) == next
+    });
+
+    let mut edges = Vec::new();
+    let mut outgoing = vec![Vec::new(); nodes.len()];
+    for (from, &start) in nodes.iter().enumerate() {
+        'corridor: for (dir, first) in grid.neighbors(start) {
+            if !open(first) || !can_leave(start, dir) {
+                continue;
+            }
+            let mut path = vec![start, first];
+            while !index.contains_key(path.last().unwrap()) {
+                let at = path[path.len() - 1];
+                let prev = path[path.len() - 2];
+                let next = grid
+                    .neighbors(at)
+                    .find(|&(d, n)| n != prev && open(n) && can_leave(at, d));
+                match next {
+                    Some((_, n)) => path.push(n),
+                    None => continue 'corridor, // dead end or blocked by one way cell
+                }
+            }
+            let to = index[path.last().unwrap()];
+            outgoing[from].push(edges.len());
+            edges.push(Edge {
+                from,
+                to,
+                steps: path.len() - 1,
+                path,
+            });
+        }
+    }
+
+    JunctionGraph {
+        nodes,
+        edges,
+        index,
+        outgoing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "\
+#.###
+#...#
+#.#.#
+#...#
+###.#";
+
+    #[test]
+    fn loop_is_contracted() {
+        let grid = Grid::parse(MAZE);
+        let graph = junction_graph(&grid, &[(0, 1), (4, 3)], |&c| c != '#');
+        let start = graph.node_index((0, 1)).unwrap();
+        let goal = graph.node_index((4, 3)).unwrap();
+        assert_eq!(graph.nodes.len(), 4); // terminals + the two T-junctions
+        let from_start: Vec<_> = graph.outgoing(start).collect();
+        assert_eq!(from_start.len(), 1);
+        assert_eq!(from_start[0].path, vec![(0, 1), (1, 1)]);
+        let steps: Vec<_> = graph.outgoing(goal).map(|e| e.steps).collect();
+        assert_eq!(steps, vec![1]);
+    }
+
+    #[test]
+    fn one_way_cells_block_corridors() {
+        let grid = Grid::parse("#.#\n#v#\n#.#");
+        let graph = directed_junction_graph(
+            &grid,
+            &[(0, 1), (2, 1)],
+            |&c| c != '#',
+            |&c| Dir::from_slope(c),
+        );
+        assert_eq!(graph.outgoing(0).count(), 1);
+        assert_eq!(graph.outgoing(1).count(), 0);
+        assert_eq!(graph.edges[0].steps, 2);
+    }
+}
@@ -1,8 +1,22 @@
 #![macro_use]
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use gen::{InputGenerator, Rng};
+use guard::{guarded, Guarded};
+use inputs::Source;
+use report::{Format, PartResult, Reporter};
 use std::fmt::Display;
-use std::{collections::HashMap, fs, path::PathBuf};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use std::{collections::HashMap, fs};
+
+pub use runner::{gen, guard, inputs, log, report};
+
+pub mod dominators;
+pub mod geometry;
+pub mod grid;
+#[cfg(test)]
+pub mod prop;
 
 // the return type for parts sometime its Numbers sometimes its Strings
 #[derive(Debug, PartialEq, Eq)]
@@ -21,64 +35,122 @@ impl Display for Answer {
     }
 }
 
+/// Root of this project, relative `--input` paths start here and not in the working directory.
+pub fn project_dir() -> PathBuf {
+    inputs::canonical(Path::new(env!("CARGO_MANIFEST_DIR")).to_owned())
+}
+
+pub const YEAR: u16 = 2023;
+
 pub struct Solver {
     solutions: HashMap<(u8, u8), fn(&str) -> Result<Answer>>,
+    generators: HashMap<u8, Box<dyn InputGenerator>>,
+    /// wall-clock limit per part, `None` waits forever
+    pub timeout: Option<Duration>,
 }
 
 impl Solver {
     pub fn new() -> Solver {
         Solver {
             solutions: HashMap::new(),
+            generators: HashMap::new(),
+            timeout: None,
         }
     }
     pub fn add(&mut self, day: u8, part: u8, fun: fn(&str) -> Result<Answer>) {
         self.solutions.insert((day, part), fun);
     }
-    pub fn solve(&self, opt: crate::Opt) {
+    /// printed by `--generate <size> [--seed <n>] day <day>`
+    pub fn add_generator(&mut self, day: u8, generator: impl InputGenerator + 'static) {
+        self.generators.insert(day, Box::new(generator));
+    }
+    /// a random input for `day`, the same `size` and `seed` always give the same input
+    pub fn generate(&self, day: u8, size: usize, seed: u64) -> Result<String> {
+        let generator = self
+            .generators
+            .get(&day)
+            .ok_or_else(|| anyhow!("day {:02} has no input generator", day))?;
+        Ok(generator.generate(size, &mut Rng::new(seed)))
+    }
+    /// returns false if any of the selected parts failed or gave a wrong answer
+    pub fn solve(&self, opt: crate::Opt, source: &Source, format: Format) -> bool {
+        let mut reporter = Reporter::for_days(format);
         match opt {
-            crate::Opt::All => self.solve_all(),
-            crate::Opt::Day { day, part } => self.solve_day(day, part),
-            crate::Opt::File { day, part, file } => self.solve_day_part_file(day, part, file),
+            crate::Opt::All => self.solve_all(source, &mut reporter),
+            crate::Opt::Day { day, part } => self.solve_day(day, part, source, &mut reporter),
+            crate::Opt::File { day, part, .. } => {
+                self.solve_day(day, Some(part), source, &mut reporter)
+            }
         }
+        reporter.finish()
     }
-    fn solve_all(&self) {
+    fn solve_all(&self, source: &Source, reporter: &mut Reporter) {
         for i in 1..=25 {
-            self.solve_day(i, None);
+            self.solve_day(i, None, source, reporter);
         }
     }
-    fn solve_day(&self, day: u8, part: Option<u8>) {
-        let file = format!("src/day{:02}/input.txt", day);
-        match fs::read_to_string(&file) {
-            Ok(content) => {
-                if let Some(part) = part {
-                    self.solve_day_part_content(day, part, &content)
-                } else {
-                    self.solve_day_part_content(day, 1, &content);
-                    self.solve_day_part_content(day, 2, &content);
-                }
-            }
-            Err(err) => println!("Error reading {}: {}", file, err),
+    fn solve_day(&self, day: u8, part: Option<u8>, source: &Source, reporter: &mut Reporter) {
+        let parts = match part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        };
+        let content = source.read(YEAR, day);
+        for part in parts {
+            let result = self
+                .solve_day_part(day, part, &content)
+                .with_input(source.origin(YEAR, day));
+            // the known answers only hold for the puzzle input
+            let expected = match source {
+                Source::Store(variant) if variant == "input" => known_answer(day, part),
+                _ => None,
+            };
+            reporter.report(result.with_expected(expected));
         }
     }
-    fn solve_day_part_file(&self, day: u8, part: u8, file: PathBuf) {
-        match fs::read_to_string(&file) {
-            Ok(content) => self.solve_day_part_content(day, part, &content),
-            Err(err) => println!("Error reading {}: {}", file.display(), err),
+    fn solve_day_part(&self, day: u8, part: u8, content: &Result<String>) -> PartResult {
+        let result = PartResult::new(YEAR, day, part);
+        match content {
+            Ok(content) => self.solve_day_part_content(result, content),
+            Err(err) => result.with_outcome::<Answer>(&Err(anyhow!("{:#}", err))),
         }
     }
-    fn solve_day_part_content(&self, day: u8, part: u8, content: &str) {
-        print!("day{:02} part{:02}: ", day, part);
-        if let Some(fun) = self.solutions.get(&(day, part)) {
-            match fun(content) {
-                Ok(answer) => println!("{}", answer),
-                Err(err) => println!("Err: implementation failed with: {}", err),
+    fn solve_day_part_content(&self, result: PartResult, content: &str) -> PartResult {
+        let Some(fun) = self.solutions.get(&(result.day, result.part)) else {
+            return result
+                .with_outcome::<Answer>(&Err(anyhow!("No solution was added to solver!")));
+        };
+        let fun = *fun;
+        let content = content.to_owned();
+        let outcome = guarded(self.timeout, move || {
+            let start = Instant::now();
+            let answer = fun(&content);
+            (answer, start.elapsed().as_nanos())
+        });
+        match outcome {
+            Guarded::Done((answer, solve_ns)) => {
+                let mut result = result.with_outcome(&answer);
+                // parsing happens inside the parts, so it is included in solve_ns
+                result.solve_ns = Some(solve_ns);
+                result
             }
-        } else {
-            println!("Err: No solution was added to solver!");
+            Guarded::Panicked(msg) => result.with_panic(&msg),
+            Guarded::TimedOut => result.with_timeout(self.timeout.unwrap()),
         }
     }
 }
 
+/// the accepted answer for the puzzle input of a day, `src/dayNN/answers.txt`
+/// has one `partN: <answer>` line per part
+fn known_answer(day: u8, part: u8) -> Option<String> {
+    let path = project_dir().join(format!("src/day{:02}/answers.txt", day));
+    let answers = fs::read_to_string(path).ok()?;
+    let prefix = format!("part{}:", part);
+    answers
+        .lines()
+        .find_map(|l| l.strip_prefix(&prefix))
+        .map(|a| a.trim().to_owned())
+}
+
 macro_rules! regex {
     ($re:literal) => {{
         static RE: std::sync::LazyLock<regex::Regex> = std::sync::LazyLock::new(|| {
@@ -92,11 +164,8 @@ macro_rules! regex {
 #[cfg(test)]
 #[macro_use]
 pub mod test_utils {
-    use std::fs;
-    pub fn read_from_file(filename: &str) -> String {
-        println!("reading {}", filename);
-        fs::read_to_string(filename)
-            .unwrap_or_else(|msg| panic!("error reading {}: {}", filename, msg))
+    pub fn read_from_store(day: &str, variant: &str) -> String {
+        super::inputs::read_for_test(super::YEAR, day, variant)
     }
     macro_rules! extract_day_from_path {
         () => {{
@@ -110,9 +179,7 @@ pub mod test_utils {
     }
     macro_rules! local_file {
         ($file:literal) => {
-            LazyLock::new(|| {
-                test_utils::read_from_file(&format!("src/{}/{}", extract_day_from_path!(), $file))
-            })
+            LazyLock::new(|| test_utils::read_from_store(extract_day_from_path!(), $file))
         };
     }
 }
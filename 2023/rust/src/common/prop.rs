@@ -0,0 +1,143 @@
+pub use super::gen::Rng;
+use super::guard::panic_message;
+use std::{
+    env,
+    fmt::Debug,
+    panic::{self, AssertUnwindSafe},
+};
+
+/// seed of the first case unless `AOC_SEED` is set, so every run checks the same inputs
+const DEFAULT_SEED: u64 = 2023;
+/// shrinking stops after this many smaller failing inputs
+const MAX_SHRINKS: usize = 1000;
+
+/// Random valid inputs of a day, and simpler variants of one that failed.
+pub trait Generator {
+    type Value: Clone + Debug;
+    fn generate(&self, rng: &mut Rng) -> Self::Value;
+    /// candidates a bit simpler than `value`, the first one that still fails replaces it
+    fn shrink(&self, _value: &Self::Value) -> Vec<Self::Value> {
+        Vec::new()
+    }
+}
+
+fn env_or(name: &str, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+/// a panic counts as a failure as well
+fn holds<V>(property: &impl Fn(&V) -> Result<(), String>, value: &V) -> Result<(), String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| property(value))) {
+        Ok(result) => result,
+        Err(payload) => Err(format!("panicked: {}", panic_message(payload))),
+    }
+}
+
+fn shrink<G: Generator>(
+    generator: &G,
+    property: &impl Fn(&G::Value) -> Result<(), String>,
+    mut value: G::Value,
+    mut error: String,
+) -> (G::Value, String, usize) {
+    let mut shrinks = 0;
+    'smaller: while shrinks < MAX_SHRINKS {
+        for candidate in generator.shrink(&value) {
+            if let Err(candidate_error) = holds(property, &candidate) {
+                (value, error) = (candidate, candidate_error);
+                shrinks += 1;
+                continue 'smaller;
+            }
+        }
+        break;
+    }
+    (value, error, shrinks)
+}
+
+/// Checks `property` on `cases` generated values, `AOC_CASES` overrides the number. Case `i` is
+/// generated from seed `AOC_SEED + i`, so the reported seed reproduces a failure as the first case.
+/// The failing value is shrunk before it is reported.
+pub fn check<G: Generator>(
+    generator: &G,
+    cases: u64,
+    property: impl Fn(&G::Value) -> Result<(), String>,
+) {
+    let seed = env_or("AOC_SEED", DEFAULT_SEED);
+    for case in 0..env_or("AOC_CASES", cases) {
+        let case_seed = seed.wrapping_add(case);
+        let value = generator.generate(&mut Rng::new(case_seed));
+        if let Err(error) = holds(&property, &value) {
+            let (value, error, shrinks) = shrink(generator, &property, value, error);
+            panic!(
+                "failed with AOC_SEED={}, shrunk {} times to\n{:#?}\n{}",
+                case_seed, shrinks, value, error
+            );
+        }
+    }
+}
+
+/// Differential test, `old` and `new` have to agree on every generated value.
+pub fn agree<G: Generator, T: PartialEq + Debug>(
+    generator: &G,
+    cases: u64,
+    old: impl Fn(&G::Value) -> T,
+    new: impl Fn(&G::Value) -> T,
+) {
+    check(generator, cases, |value| match (old(value), new(value)) {
+        (old, new) if old == new => Ok(()),
+        (old, new) => Err(format!("old {:?} != new {:?}", old, new)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Numbers;
+
+    impl Generator for Numbers {
+        type Value = Vec<i128>;
+        fn generate(&self, rng: &mut Rng) -> Vec<i128> {
+            (0..rng.below(20)).map(|_| rng.between(-50, 50)).collect()
+        }
+        fn shrink(&self, value: &Vec<i128>) -> Vec<Vec<i128>> {
+            let mut smaller = Vec::new();
+            for i in 0..value.len() {
+                let mut removed = value.clone();
+                removed.remove(i);
+                smaller.push(removed);
+                if value[i] != 0 {
+                    let mut closer = value.clone();
+                    closer[i] -= value[i].signum();
+                    smaller.push(closer);
+                }
+            }
+            smaller
+        }
+    }
+
+    #[test]
+    fn seeds_reproduce_and_failures_shrink() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        assert!((0..100).all(|_| a.between(-3, 3) == b.between(-3, 3)));
+
+        agree(
+            &Numbers,
+            200,
+            |v| v.iter().sum::<i128>(),
+            |v| v.iter().rev().sum(),
+        );
+        // the sum only goes wrong once there is something big, the smallest input is one number
+        let wrong_sum = |v: &Vec<i128>| -> i128 { v.iter().map(|&x| x.min(30)).sum() };
+        let failure = panic::catch_unwind(|| {
+            agree(&Numbers, 200, |v| v.iter().sum::<i128>(), wrong_sum);
+        })
+        .unwrap_err();
+        let message = panic_message(failure);
+        assert!(message.contains("AOC_SEED="), "{}", message);
+        assert!(message.contains("[\n    31,\n]"), "{}", message);
+    }
+}
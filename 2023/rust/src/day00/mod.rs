@@ -23,14 +23,14 @@ fn parse_input(input: &str) -> Result<Input> {
 fn solve_one(input: &Input) -> Result<Answer> {
     let Input { nums } = input;
     let sum = nums.iter().sum::<i128>();
-    println!("sum = {}", sum);
+    debug!("sum = {}", sum);
     Ok(Answer::Num(-1))
 }
 
 fn solve_two(input: &Input) -> Result<Answer> {
     let Input { nums } = input;
     let squared_sum = nums.iter().map(|n| n * n).sum::<i128>();
-    println!("squared sum = {}", squared_sum);
+    debug!("squared sum = {}", squared_sum);
     Ok(Answer::Num(-1))
 }
 
@@ -40,8 +40,8 @@ mod tests {
     use std::sync::LazyLock;
     use test::Bencher;
 
-    static TEST: LazyLock<String> = local_file!("test.txt");
-    static INPUT: LazyLock<String> = local_file!("input.txt");
+    static TEST: LazyLock<String> = local_file!("test");
+    static INPUT: LazyLock<String> = local_file!("input");
 
     #[test]
     fn test_one() -> Result<()> {
@@ -87,9 +87,9 @@ mod tests {
     use std::sync::LazyLock;
     use test::Bencher;
 
-    static TEST: LazyLock<String> = local_file!("test.txt");
-    static TEST2: LazyLock<String> = local_file!("test2.txt");
-    static INPUT: LazyLock<String> = local_file!("input.txt");
+    static TEST: LazyLock<String> = local_file!("test");
+    static TEST2: LazyLock<String> = local_file!("test2");
+    static INPUT: LazyLock<String> = local_file!("input");
 
     #[test]
     fn test_one() -> Result<()> {
@@ -61,7 +61,7 @@ fn solve_one(input: &Input) -> Result<Answer> {
     for card in cards {
         if card.matching > 0 {
             let card_worth = 2_i32.pow(card.matching as u32 - 1);
-            println!("{}", card_worth);
+            trace!("{}", card_worth);
             total += card_worth
         }
     }
@@ -100,8 +100,8 @@ mod tests {
     use std::sync::LazyLock;
     use test::Bencher;
 
-    static TEST: LazyLock<String> = local_file!("test.txt");
-    static INPUT: LazyLock<String> = local_file!("input.txt");
+    static TEST: LazyLock<String> = local_file!("test");
+    static INPUT: LazyLock<String> = local_file!("input");
 
     #[test]
     fn test_one() -> Result<()> {
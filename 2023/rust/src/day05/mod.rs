@@ -82,7 +82,7 @@ impl Input {
         // println!("{:?}", seed_range);
         // println!("{:?}", result);
         let result: Vec<(usize, usize)> = result.into_iter().collect();
-        assert_eq!(len, result.iter().map(|(_, l)| l).sum());
+        assert_eq!(len, result.iter().map(|(_, l)| l).sum::<usize>());
         result
     }
     fn map_range_tuple(&self, rt: (usize, usize), mapping: &Mapping) -> (usize, usize) {
@@ -202,8 +202,8 @@ mod tests {
     use std::sync::LazyLock;
     use test::Bencher;
 
-    static TEST: LazyLock<String> = local_file!("test.txt");
-    static INPUT: LazyLock<String> = local_file!("input.txt");
+    static TEST: LazyLock<String> = local_file!("test");
+    static INPUT: LazyLock<String> = local_file!("input");
 
     #[test]
     fn test_one() -> Result<()> {
@@ -35,7 +35,7 @@ impl Race {
                 wins += 1;
             }
         }
-        println!("wins: {}", wins);
+        trace!("wins: {}", wins);
         wins
     }
 
@@ -98,8 +98,8 @@ mod tests {
     use std::sync::LazyLock;
     use test::Bencher;
 
-    static TEST: LazyLock<String> = local_file!("test.txt");
-    static INPUT: LazyLock<String> = local_file!("input.txt");
+    static TEST: LazyLock<String> = local_file!("test");
+    static INPUT: LazyLock<String> = local_file!("input");
 
     #[test]
     fn test_one() -> Result<()> {
@@ -1,3 +1,4 @@
+use super::common::geometry;
 use super::common::*;
 use anyhow::Result;
 use std::collections::{HashMap, HashSet, VecDeque};
@@ -14,13 +15,9 @@ pub fn part_two(input: &str) -> Result<Answer> {
 
 struct Input {
     grid: Vec<Vec<char>>,
-    xsize: i128,
-    ysize: i128,
     neighbours: HashMap<(i128, i128), Vec<(i128, i128)>>,
     spos: (i128, i128),
     the_pipe: HashSet<(i128, i128)>,
-    intersection_count_vertical: HashMap<(i128, i128), i128>,
-    intersection_count_horizontal: HashMap<(i128, i128), i128>,
 }
 
 impl Input {
@@ -45,114 +42,48 @@ impl Input {
         distance
     }
 
-    fn replace_s(&mut self) {
-        // nesw
-        let dx = vec![0, 1, 0, -1];
-        let dy = vec![-1, 0, 1, 0];
-        let mut connector = vec![];
-        for i in 0..4 {
-            let (x, y) = self.spos;
-            let npos = (x + dx[i], y + dy[i]);
-            if let Some(neighs) = self.neighbours.get(&npos) {
-                if neighs.contains(&self.spos) {
-                    connector.push(i);
-                }
-            }
-        }
-        assert_eq!(connector.len(), 2);
-        let replace = match (connector[0], connector[1]) {
-            (0, 1) => 'L',
-            (0, 2) => '|',
-            (0, 3) => 'J',
-            (1, 2) => 'F',
-            (1, 3) => '-',
-            (2, 3) => '7',
-            _ => unreachable!(),
+    /// the pipe tiles in loop order starting at S
+    fn pipe_loop(&self) -> Vec<(i128, i128)> {
+        let connected = |from: &(i128, i128), to: &(i128, i128)| {
+            self.neighbours.get(to).is_some_and(|n| n.contains(from))
         };
-        self.grid[self.spos.1 as usize][self.spos.0 as usize] = replace;
-    }
-    fn count_inside(&mut self) -> i128 {
-        self.replace_s();
-        // horizontal: | or L---7 or F---J count towards intersection
-        for y in 0..self.ysize {
-            let mut intersection_count = 0;
-            let mut started_with = '.';
-            for x in 0..self.xsize {
-                let c = self.grid[y as usize][x as usize];
-                if !self.the_pipe.contains(&(x, y)) {
-                    self.intersection_count_horizontal
-                        .insert((x, y), intersection_count);
-                } else {
-                    match c {
-                        '|' => intersection_count += 1,
-                        'L' => started_with = 'L',
-                        '7' if started_with == 'L' => {
-                            intersection_count += 1;
-                            started_with = '.';
-                        }
-                        'F' => started_with = 'F',
-                        'J' if started_with == 'F' => {
-                            intersection_count += 1;
-                            started_with = '.';
-                        }
-                        '-' => {}
-                        _ => started_with = '.',
-                    }
-                }
-            }
-        }
-        // vertical: - or 7 or F
-        //                |    |
-        //                |    |
-        //                |    |
-        //                L    J
-        for x in 0..self.xsize {
-            let mut intersection_count = 0;
-            let mut started_with = '.';
-            for y in 0..self.ysize {
-                let c = self.grid[y as usize][x as usize];
-                if !self.the_pipe.contains(&(x, y)) {
-                    self.intersection_count_vertical
-                        .insert((x, y), intersection_count);
-                } else {
-                    match c {
-                        '-' => intersection_count += 1,
-                        '7' => started_with = '7',
-                        'L' if started_with == '7' => {
-                            intersection_count += 1;
-                            started_with = '.';
-                        }
-                        'F' => started_with = 'F',
-                        'J' if started_with == 'F' => {
-                            intersection_count += 1;
-                            started_with = '.';
-                        }
-                        '|' => {}
-                        _ => started_with = '.',
-                    }
-                }
-            }
+        let mut pipe_loop = vec![self.spos];
+        let mut prev = self.spos;
+        let mut cur = *self.neighbours[&self.spos]
+            .iter()
+            .find(|n| connected(&self.spos, n))
+            .unwrap();
+        while cur != self.spos {
+            pipe_loop.push(cur);
+            let next = *self.neighbours[&cur]
+                .iter()
+                .find(|&n| *n != prev && connected(&cur, n))
+                .unwrap();
+            prev = cur;
+            cur = next;
         }
-        let mut inner_count = 0;
-        for (y, line) in self.grid.iter().enumerate() {
-            for (x, c) in line.iter().enumerate() {
-                let xi = x as i128;
-                let yi = y as i128;
-                if let Some(hori) = self.intersection_count_horizontal.get(&(xi, yi)) {
-                    let vert = self.intersection_count_vertical.get(&(xi, yi)).unwrap();
-                    if hori % 2 != 0 && vert % 2 != 0 {
-                        print!("I");
-                        inner_count += 1;
+        pipe_loop
+    }
+    fn count_inside(&self) -> i128 {
+        let pipe_loop = self.pipe_loop();
+        if log::enabled(log::Level::Trace, module_path!()) {
+            let mut view = String::new();
+            for (y, line) in self.grid.iter().enumerate() {
+                for (x, c) in line.iter().enumerate() {
+                    let pos = (x as i128, y as i128);
+                    if self.the_pipe.contains(&pos) {
+                        view.push(*c);
+                    } else if geometry::contains(&pipe_loop, pos) {
+                        view.push('I');
                     } else {
-                        print!("O");
+                        view.push('O');
                     }
-                } else {
-                    print!("{}", c);
                 }
+                view.push('\n');
             }
-            println!();
+            trace!("inside (I) and outside (O) tiles\n{}", view);
         }
-        inner_count
+        geometry::interior_points(&pipe_loop)
     }
 }
 
@@ -211,13 +142,9 @@ fn parse_input(input: &str) -> Result<Input> {
     assert_ne!((-1, -1), spos);
     Ok(Input {
         grid,
-        xsize,
-        ysize,
         neighbours,
         spos,
         the_pipe: HashSet::new(),
-        intersection_count_vertical: HashMap::new(),
-        intersection_count_horizontal: HashMap::new(),
     })
 }
 
@@ -236,11 +163,11 @@ mod tests {
     use std::sync::LazyLock;
     use test::Bencher;
 
-    static TEST: LazyLock<String> = local_file!("test.txt");
-    static TEST_TWO_EASY: LazyLock<String> = local_file!("test_two_easy.txt");
-    static TEST_TWO_HARD: LazyLock<String> = local_file!("test_two_hard.txt");
-    static TEST_TWO_HARDEST: LazyLock<String> = local_file!("test_two_hardest.txt");
-    static INPUT: LazyLock<String> = local_file!("input.txt");
+    static TEST: LazyLock<String> = local_file!("test");
+    static TEST_TWO_EASY: LazyLock<String> = local_file!("test_two_easy");
+    static TEST_TWO_HARD: LazyLock<String> = local_file!("test_two_hard");
+    static TEST_TWO_HARDEST: LazyLock<String> = local_file!("test_two_hardest");
+    static INPUT: LazyLock<String> = local_file!("input");
 
     #[test]
     fn test_one() -> Result<()> {
@@ -1,7 +1,5 @@
-use std::collections::HashMap;
-
 use super::common::*;
-use anyhow::Result;
+use anyhow::{Context, Result};
 
 pub fn part_one(input: &str) -> Result<Answer> {
     let input = parse_input(input)?;
@@ -13,298 +11,164 @@ pub fn part_two(input: &str) -> Result<Answer> {
     solve_two(&input)
 }
 
-struct Input {
-    springs: Vec<Vec<char>>,
-    records: Vec<Vec<usize>>,
-}
-
-fn parse_input(input: &str) -> Result<Input> {
-    let mut springs = Vec::new();
-    let mut records = Vec::new();
-    for line in input.lines() {
-        let (springs_line, records_line) = line.split_once(" ").unwrap();
-        let springs_line = springs_line.trim().chars().collect();
-        springs.push(springs_line);
-        let records_line = records_line
-            .split(",")
-            .map(|s| s.trim().parse().unwrap())
-            .collect();
-        records.push(records_line);
-    }
-    Ok(Input { springs, records })
-}
-
-fn solve_line(springs: &[char], records: &[usize]) -> usize {
-    // for each ? either can be . or replaced with group and progress
-    let mut count = 0;
-    solve_helper(springs, records, 0, &mut count);
-    count
+/// One nonogram row: springs that are operational `.`, damaged `#` or unknown `?`, and the
+/// lengths of the runs of damaged springs in order.
+#[derive(Debug, Clone)]
+struct Row {
+    springs: Vec<char>,
+    groups: Vec<usize>,
 }
 
-fn solve_helper(springs: &[char], records: &[usize], spos: usize, count: &mut usize) {
-    // println!("{:?}, {:?}, {}, {}", springs, records, spos, count);
-    if spos == springs.len() && !is_invalid(springs, records) {
-        // let springs_string: String = springs.iter().collect();
-        // println!("{}", springs_string);
-        *count += 1;
-        return;
-    }
-    if springs[spos] != '?' {
-        solve_helper(springs, records, spos + 1, count)
-    } else {
-        let mut next_springs: Vec<char> = springs.iter().map(|&c| c).collect();
-        next_springs[spos] = '.';
-        if !is_invalid(&next_springs, records) {
-            solve_helper(&next_springs, records, spos + 1, count);
+impl Row {
+    /// `factor` copies of the springs joined by `?` and of the groups
+    fn unfold(&self, factor: usize) -> Row {
+        let mut springs = Vec::with_capacity((self.springs.len() + 1) * factor);
+        for i in 0..factor {
+            if i != 0 {
+                springs.push('?');
+            }
+            springs.extend_from_slice(&self.springs);
         }
-        next_springs[spos] = '#';
-        if !is_invalid(&next_springs, records) {
-            solve_helper(&next_springs, records, spos + 1, count);
+        Row {
+            springs,
+            groups: self.groups.repeat(factor),
         }
     }
-}
 
-fn is_invalid(springs: &[char], records: &[usize]) -> bool {
-    let cheat_springs: String = springs.iter().collect();
-    let cheat_springs = format!(".{}.", cheat_springs);
-    let cheats: Vec<char> = cheat_springs.chars().collect();
-    let record_sum = records.iter().sum();
-    let count_hashtag = count_c(&cheat_springs, '#');
-    let count_questionmark = count_c(&cheat_springs, '?');
-    if count_hashtag + count_questionmark < record_sum {
-        return true;
-    }
-    if count_hashtag > record_sum {
-        return true;
+    /// State after a spring: (completed groups, length of the current run) or `None` if `spring`
+    /// can't come next.
+    fn step(&self, (group, run): (usize, usize), spring: char) -> Option<(usize, usize)> {
+        match spring {
+            '.' if run == 0 => Some((group, 0)),
+            '.' if run == self.groups[group] => Some((group + 1, 0)),
+            '#' if group < self.groups.len() && run < self.groups[group] => Some((group, run + 1)),
+            _ => None,
+        }
     }
-    if !scan(&cheat_springs, records) {
-        return true;
+    fn accepts(&self, (group, run): (usize, usize)) -> bool {
+        let groups = self.groups.len();
+        (group == groups && run == 0) || (group + 1 == groups && run == self.groups[group])
     }
-    if count_questionmark == 0 {
-        let re_finished_group = regex!(r"(#+)");
-        // println!("checking {}", cheat_springs);
-        let caps: Vec<&str> = re_finished_group
-            .captures_iter(&cheat_springs)
-            .map(|c| c.get(1).unwrap())
-            .filter(|c| cheats[c.start() - 1] == '.' && cheats[c.end()] == '.')
-            .map(|c| c.as_str())
-            .collect();
-        if caps.len() > records.len() {
-            return true;
+
+    /// Number of ways to fill in the `?`, a DP over (position, group index, run length).
+    fn arrangements(&self) -> usize {
+        let longest = self.groups.iter().max().copied().unwrap_or(0);
+        // counts[group][run] for all rows that agree with the springs so far
+        let mut counts = vec![vec![0_usize; longest + 1]; self.groups.len() + 1];
+        counts[0][0] = 1;
+        for &spring in self.springs.iter() {
+            let mut next = vec![vec![0_usize; longest + 1]; self.groups.len() + 1];
+            for (group, runs) in counts.iter().enumerate() {
+                for (run, &count) in runs.iter().enumerate().filter(|(_, &c)| c > 0) {
+                    for choice in ['.', '#'] {
+                        if spring != '?' && spring != choice {
+                            continue;
+                        }
+                        if let Some((g, r)) = self.step((group, run), choice) {
+                            next[g][r] += count;
+                        }
+                    }
+                }
+            }
+            counts = next;
         }
-        // println!("caps: {:?}", caps);
-        for (i, cap) in caps.into_iter().enumerate().rev() {
-            // println!("{}: captured {} (record {})", i, cap, records[i]);
-            if cap.len() != records[i] {
-                return true;
+        let mut total = 0;
+        for (group, runs) in counts.iter().enumerate() {
+            for (run, &count) in runs.iter().enumerate() {
+                if self.accepts((group, run)) {
+                    total += count;
+                }
             }
         }
+        total
     }
-    false
-}
 
-fn scan(springs: &str, records: &[usize]) -> bool {
-    let springs: Vec<char> = springs.chars().collect();
-    let mut connected = 0;
-    let mut ri = 0;
-    for i in 0..springs.len() {
-        let c = springs[i];
-        if c == '?' {
-            return ri >= records.len() || connected <= records[ri];
-        } else if c == '#' {
-            connected += 1;
-        } else if connected != 0 {
-            if ri >= records.len() || connected != records[ri] {
-                return false;
+    /// All arrangements spelled out, exponential in the number of `?` so only for debugging
+    /// small rows.
+    fn enumerate(&self) -> Vec<String> {
+        fn fill(row: &Row, state: (usize, usize), filled: &mut String, out: &mut Vec<String>) {
+            let Some(&spring) = row.springs.get(filled.len()) else {
+                if row.accepts(state) {
+                    out.push(filled.clone());
+                }
+                return;
+            };
+            for choice in ['.', '#'] {
+                if spring != '?' && spring != choice {
+                    continue;
+                }
+                if let Some(next) = row.step(state, choice) {
+                    filled.push(choice);
+                    fill(row, next, filled, out);
+                    filled.pop();
+                }
             }
-            connected = 0;
-            ri += 1;
         }
+        let mut out = Vec::new();
+        fill(self, (0, 0), &mut String::new(), &mut out);
+        out
     }
-    // println!("springs {:?}, records {:?}", springs, records);
-
-    return ri >= records.len();
 }
 
-fn count_c(line: &str, c: char) -> usize {
-    line.chars().filter(|&lc| lc == c).count()
+struct Input {
+    rows: Vec<Row>,
 }
 
-fn solve_one(input: &Input) -> Result<Answer> {
-    let Input { springs, records } = input;
-    let mut sum = 0;
-    for (springs, records) in springs.iter().zip(records.iter()) {
-        sum += solve_line(&springs, &records);
+fn parse_input(input: &str) -> Result<Input> {
+    let mut rows = Vec::new();
+    for line in input.lines() {
+        let (springs, groups) = line
+            .split_once(" ")
+            .with_context(|| format!("no space in {}", line))?;
+        let groups = groups
+            .split(",")
+            .map(|s| s.trim().parse())
+            .collect::<Result<Vec<usize>, _>>()?;
+        rows.push(Row {
+            springs: springs.trim().chars().collect(),
+            groups,
+        });
     }
-    Ok(Answer::Num(sum as i128))
+    Ok(Input { rows })
 }
 
-fn solve_line_two(springs: &[char], records: &[usize]) -> usize {
-    let mut memo = HashMap::new();
-    let rem = springs.iter().map(|&c| c).collect();
-    let mat: Vec<char> = Vec::new();
-    let des_len = springs.len();
-    solve_help(&mut memo, rem, records, 0, mat, des_len)
-}
-fn solve_help(
-    memo: &mut HashMap<(String, usize), usize>,
-    rem: Vec<char>,
-    records: &[usize],
-    rpos: usize,
-    mat: Vec<char>,
-    des_len: usize,
-) -> usize {
-    // let matstr = String::from_iter(mat.iter());
-    let remstr = String::from_iter(rem.iter());
-    // println!(
-    //     "calling mat={} rem={} rec={:?}",
-    //     matstr,
-    //     remstr,
-    //     &records[rpos..]
-    // );
-    if let Some(count) = memo.get(&(remstr.clone(), rpos)) {
-        // println!(
-        //     "{} || {},{}({:?}) = {}",
-        //     matstr.clone(),
-        //     remstr.clone(),
-        //     rpos,
-        //     &records[rpos..],
-        //     count
-        // );
-        return *count;
-    }
-    if rpos >= records.len() {
-        if rem.contains(&'#') {
-            return 0; // invalid
-        } else {
-            let matstr = String::from_iter(mat.iter());
-            let remstr = String::from_iter(rem.iter());
-            // println!("{} || {}", matstr, remstr);
-            let complete_str = format!("{}{}", matstr, remstr).replace("?", ".");
-            assert_eq!(des_len, complete_str.len());
-            if scan(&complete_str, &records) {
-                // println!("{} complete", complete_str);
-                return 1;
-            } else {
-                return 0;
-            }
-        }
-    }
-    // try placing .####. if records is 4
-    // e.g. .?????.##
-    // plac .####..## next with ..##
-    // plac ..####.## next with .##
-    // plac ...###### not possible! done
-    let place = format!(".{}.", "#".repeat(records[rpos]));
-    let place: Vec<char> = place.chars().collect();
-    let mut count: usize = 0;
-    for i in 0..rem.len() {
-        let mut valid = true;
-        for j in 0..place.len() {
-            if i + j >= rem.len() || !(rem[i + j] == '?' || rem[i + j] == place[j]) {
-                valid = false;
-                break;
-            }
-        }
-        if valid {
-            let start_rem = i + place.len() - 1;
-            let mut matadd = Vec::from_iter(rem[0..start_rem].iter().map(|&c| c));
-            for j in 0..place.len() - 1 {
-                matadd[i + j] = place[j];
-            }
-            let mut next_rem = Vec::from_iter(rem[start_rem..].iter().map(|&c| c));
-            assert_ne!(next_rem[0], '#');
-            next_rem[0] = '.'; // important because it might have been ? before
-            let mut next_mat = mat.clone();
-            next_mat.append(&mut matadd);
-
-            // println!(
-            //     "{}: {}, {}, {}, {}",
-            //     i,
-            //     String::from_iter(rem.iter()),
-            //     String::from_iter(place.iter()),
-            //     String::from_iter(next_mat.iter()),
-            //     String::from_iter(next_rem.iter())
-            // );
-            // check whether matstr+remstr can still be valid
-            let next_matstr =
-                String::from_iter(next_mat.iter().map(|&c| if c == '?' { '.' } else { c }));
-            let current = format!("{}{}", next_matstr, String::from_iter(next_rem.iter()));
-            let ok =
-                scan(&format!("{}.", next_matstr), &records[..rpos + 1]) && scan(&current, records);
-            // println!("{}  {}", current, ok);
-            if ok {
-                // println!(
-                //     "{}: {}, {}, {}, {}",
-                //     i,
-                //     String::from_iter(rem.iter()),
-                //     String::from_iter(place.iter()),
-                //     String::from_iter(next_mat.iter()),
-                //     String::from_iter(next_rem.iter())
-                // );
-                let add = solve_help(memo, next_rem.clone(), records, rpos + 1, next_mat, des_len);
-                //memo.insert((String::from_iter(next_rem.iter()), rpos + 1), add);
-                count += add;
+fn total_arrangements(input: &Input, factor: usize) -> usize {
+    let mut sum = 0;
+    for row in input.rows.iter().map(|row| row.unfold(factor)) {
+        let count = row.arrangements();
+        trace!(
+            "{} {:?} = {}",
+            String::from_iter(row.springs.iter()),
+            row.groups,
+            count
+        );
+        if log::enabled(log::Level::Trace, module_path!()) && row.springs.len() <= 20 {
+            for arrangement in row.enumerate() {
+                trace!("  {}", arrangement);
             }
         }
+        sum += count;
     }
-
-    let current = format!(
-        "{}{}",
-        String::from_iter(mat.iter().map(|&c| if c == '?' { '.' } else { c })),
-        String::from_iter(rem.iter())
-    );
-    let ok = scan(&current, records);
-    if ok {
-        // println!(
-        //     "inserting ({}, {}({:?})) = {}",
-        //     remstr,
-        //     rpos,
-        //     &records[rpos..],
-        //     count
-        // );
-        memo.insert((remstr, rpos), count);
-    }
-    count
+    sum
 }
 
-//
+fn solve_one(input: &Input) -> Result<Answer> {
+    Ok(Answer::Num(total_arrangements(input, 1) as i128))
+}
 
 fn solve_two(input: &Input) -> Result<Answer> {
-    let Input { springs, records } = input;
-    let mut sum = 0;
-    for (springs, records) in springs.iter().zip(records.iter()).rev() {
-        let mut rsprings = Vec::new();
-        rsprings.push('.'); // makes processing later easier!
-        for i in 0..5 {
-            rsprings.append(&mut springs.clone());
-            if i != 4 {
-                rsprings.push('?')
-            }
-        }
-        rsprings.push('.'); // makes processing later easier!
-        let records: Vec<usize> = records
-            .into_iter()
-            .cycle()
-            .take(records.len() * 5)
-            .map(|&c| c)
-            .collect();
-        let answer = solve_line_two(&rsprings, &records);
-        sum += answer;
-        let sprstr = String::from_iter(rsprings.iter());
-        println!("solved {}, {:?} = {}", sprstr, records, answer);
-    }
-    Ok(Answer::Num(sum as i128))
+    Ok(Answer::Num(total_arrangements(input, 5) as i128))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::prop::{self, Generator, Rng};
     use std::sync::LazyLock;
     use test::Bencher;
 
-    static TEST: LazyLock<String> = local_file!("test.txt");
-    static INPUT: LazyLock<String> = local_file!("input.txt");
+    static TEST: LazyLock<String> = local_file!("test");
+    static INPUT: LazyLock<String> = local_file!("input");
 
     #[test]
     fn test_one() -> Result<()> {
@@ -324,54 +188,25 @@ mod tests {
         assert_eq!(answer, Answer::Num(525152));
         Ok(())
     }
-    // #[test]
-    // fn test_solve_two() -> Result<()> {
-    //     let a: Vec<char> = ".?###??????????###????????.".chars().collect();
-    //     let ans = super::solve_line_two(&a[..], &[3, 2, 1, 3, 2, 1]);
-    //     assert_eq!(ans, 1234);
-    //     Ok(())
-    // }
-    // #[test]
-    // fn aaa() -> Result<()> {
-    //     let a: Vec<char> = ".??????????###????????.".chars().collect();
-    //     let ans = super::solve_line_two(&a[..], &[3, 2, 1, 3]);
-    //     assert_eq!(ans, 1234);
-    //     Ok(())
-    // }
-    // #[test]
-    // fn bbb() -> Result<()> {
-    //     let a: Vec<char> = ".#?.?.#??#???#.?.???#?.?.#??#???#.?.???#?.?.#??#???#.?.???#?.?.#??#???#.?.???#?.?.#??#???#.?.??.".chars().collect();
-    //     let ans = super::solve_line_two(
-    //         &a[..],
-    //         &[
-    //             2, 1, 1, 2, 2, 2, 1, 1, 2, 2, 2, 1, 1, 2, 2, 2, 1, 1, 2, 2, 2, 1, 1, 2, 2,
-    //         ],
-    //     );
-    //     assert_eq!(ans, 1234);
-    //     Ok(())
-    // }
-    // #[test]
-    // fn ccc() -> Result<()> {
-    //     let a: Vec<char> = ".#?.?.#??#???#.?.???#?.?.#??#???#.?.??.".chars().collect();
-    //     let ans = super::solve_line_two(&a[..], &[2, 1, 1, 2, 2, 2, 1, 1, 2, 2]);
-    //     assert_eq!(ans, 1234);
-    //     Ok(())
-    // }
-    // #[test]
-    // fn ddd() -> Result<()> {
-    //     let a: Vec<char> = ".???.?.?.?.?.###.???????.".chars().collect();
-    //     let ans = super::solve_line_two(&a[..], &[2, 3, 2, 1]);
-    //     assert_eq!(ans, 1234);
-    //     Ok(())
-    // }
+    #[test]
+    fn arrangements_match_enumeration() {
+        let row = |springs: &str, groups: &[usize]| Row {
+            springs: springs.chars().collect(),
+            groups: groups.to_vec(),
+        };
+        let small = row("?###????????", &[3, 2, 1]);
+        assert_eq!(small.arrangements(), 10);
+        assert_eq!(small.enumerate().len(), 10);
+        assert!(small.enumerate().contains(&".###.##.#...".to_owned()));
+        assert_eq!(
+            row(".??..??...?##.", &[1, 1, 3]).unfold(5).arrangements(),
+            16384
+        );
+        assert_eq!(row("???.###", &[1, 1, 3]).unfold(2).arrangements(), 1);
+        assert_eq!(row("#.#", &[1]).arrangements(), 0);
+        assert_eq!(row("...", &[]).arrangements(), 1);
+    }
 
-    // #[test]
-    // fn eee() -> Result<()> {
-    //     let a: Vec<char> = ".?#???#.?.??.".chars().collect();
-    //     let ans = super::solve_line_two(&a[..], &[1, 2, 2]);
-    //     assert_eq!(ans, 1234);
-    //     Ok(())
-    // }
     #[test]
     fn part_two() -> Result<()> {
         let answer = super::part_two(&INPUT)?;
@@ -379,6 +214,84 @@ mod tests {
         Ok(())
     }
 
+    /// rows of up to 14 springs, the groups come from a random filling so most rows fit
+    struct Rows;
+
+    impl Generator for Rows {
+        type Value = Row;
+        fn generate(&self, rng: &mut Rng) -> Row {
+            let filled: Vec<char> = (0..1 + rng.below(14))
+                .map(|_| if rng.chance(40) { '#' } else { '.' })
+                .collect();
+            let groups = filled
+                .split(|&c| c == '.')
+                .map(|run| run.len())
+                .filter(|&len| len > 0)
+                .collect();
+            let springs = filled
+                .into_iter()
+                .map(|c| if rng.chance(50) { '?' } else { c })
+                .collect();
+            Row { springs, groups }
+        }
+        fn shrink(&self, row: &Row) -> Vec<Row> {
+            let mut smaller = Vec::new();
+            for i in 0..row.springs.len() {
+                let mut fewer = row.clone();
+                fewer.springs.remove(i);
+                smaller.push(fewer);
+                if row.springs[i] == '?' {
+                    for spring in ['.', '#'] {
+                        let mut known = row.clone();
+                        known.springs[i] = spring;
+                        smaller.push(known);
+                    }
+                }
+            }
+            for i in 0..row.groups.len() {
+                let mut fewer = row.clone();
+                fewer.groups.remove(i);
+                smaller.push(fewer);
+            }
+            smaller
+        }
+    }
+
+    /// every filling of the `?`, checked by its runs of `#`
+    fn brute_force(row: &Row) -> usize {
+        let unknown: Vec<usize> = (0..row.springs.len())
+            .filter(|&i| row.springs[i] == '?')
+            .collect();
+        (0..1_usize << unknown.len())
+            .filter(|mask| {
+                let mut springs = row.springs.clone();
+                for (bit, &i) in unknown.iter().enumerate() {
+                    springs[i] = if mask >> bit & 1 == 1 { '#' } else { '.' };
+                }
+                let runs: Vec<usize> = springs
+                    .split(|&c| c == '.')
+                    .map(|run| run.len())
+                    .filter(|&len| len > 0)
+                    .collect();
+                runs == row.groups
+            })
+            .count()
+    }
+
+    #[test]
+    fn arrangements_agree_with_brute_force() {
+        prop::check(&Rows, 500, |row| {
+            let expected = brute_force(row);
+            match (row.arrangements(), row.enumerate().len()) {
+                (dp, listed) if dp == expected && listed == expected => Ok(()),
+                (dp, listed) => Err(format!(
+                    "brute force {}, dp {}, enumerated {}",
+                    expected, dp, listed
+                )),
+            }
+        });
+    }
+
     #[bench]
     fn bench_part_one(b: &mut Bencher) {
         b.iter(|| part_one())
@@ -176,12 +176,8 @@ fn count_energy(energized: &Vec<Vec<char>>) -> usize {
 fn solve_one(input: &Input) -> Result<Answer> {
     let Input { grid } = input;
     let energized = energize_from(0, 0, 1, &grid);
-    for r in 0..grid.len() {
-        for c in 0..grid[0].len() {
-            print!("{}", energized[r][c]);
-        }
-        println!();
-    }
+    let view: Vec<String> = energized.iter().map(String::from_iter).collect();
+    trace!("energized tiles\n{}", view.join("\n"));
     let count = count_energy(&energized);
     Ok(Answer::Num(count as i128))
 }
@@ -215,8 +211,8 @@ mod tests {
     use std::sync::LazyLock;
     use test::Bencher;
 
-    static TEST: LazyLock<String> = local_file!("test.txt");
-    static INPUT: LazyLock<String> = local_file!("input.txt");
+    static TEST: LazyLock<String> = local_file!("test");
+    static INPUT: LazyLock<String> = local_file!("input");
 
     #[test]
     fn test_one() -> Result<()> {
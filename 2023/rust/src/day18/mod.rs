@@ -1,7 +1,7 @@
-use std::collections::{HashMap, VecDeque};
-
+use super::common::geometry::{self, parse_move, polygon_from_moves};
+use super::common::grid::Dir;
 use super::common::*;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 
 pub fn part_one(input: &str) -> Result<Answer> {
     let input = parse_input(input)?;
@@ -18,369 +18,78 @@ struct Input {
 }
 
 struct DigInstr {
-    dir: char,
-    amount: usize,
+    dir: Dir,
+    amount: i128,
     color: String,
 }
 
-struct DigGrid {
-    grid: HashMap<(i128, i128), String>,
-}
-
-impl DigGrid {
-    fn new(dig_plan: &Vec<DigInstr>) -> Self {
-        let mut grid = HashMap::new();
-        let mut pos = (0, 0);
-        let mut mapper = HashMap::new();
-        mapper.insert('U', (0, -1));
-        mapper.insert('R', (1, 0));
-        mapper.insert('D', (0, 1));
-        mapper.insert('L', (-1, 0));
-        for DigInstr { dir, amount, color } in dig_plan.iter() {
-            let delta = mapper.get(dir).unwrap();
-            let mut countdown = *amount;
-            while countdown > 0 {
-                pos = (pos.0 + delta.0, pos.1 + delta.1);
-                grid.insert(pos, color.clone());
-                countdown -= 1;
-            }
-        }
-        DigGrid { grid }
-    }
-    fn get_filled_grid(&self) -> Vec<Vec<char>> {
-        let mut min_x = i128::MAX;
-        let mut min_y = i128::MAX;
-        let mut max_x = i128::MIN;
-        let mut max_y = i128::MIN;
-        for (x, y) in self.grid.keys() {
-            min_x = std::cmp::min(min_x, *x);
-            min_y = std::cmp::min(min_y, *y);
-            max_x = std::cmp::max(max_x, *x);
-            max_y = std::cmp::max(max_y, *y);
-        }
-        // for empty corners
-        min_x -= 1;
-        min_y -= 1;
-        max_x += 1;
-        max_y += 1;
-        let rows = max_y - min_y + 1;
-        let cols = max_x - min_x + 1;
-        let mut filled = vec![vec!['.'; cols as usize]; rows as usize];
-        for (x, y) in self.grid.keys() {
-            let x = x - min_x;
-            let y = y - min_y;
-            filled[y as usize][x as usize] = '#';
+impl DigInstr {
+    // the real instruction is hidden in the color: 5 hex digits amount, 1 digit direction
+    fn decode_color(&self) -> Result<(Dir, i128)> {
+        let hex = self.color.trim_start_matches("(#").trim_end_matches(')');
+        if hex.len() != 6 {
+            return Err(anyhow!("invalid color {}", self.color));
         }
-        filled
+        let amount = i128::from_str_radix(&hex[0..5], 16)?;
+        let dir = match &hex[5..6] {
+            "0" => Dir::Right,
+            "1" => Dir::Down,
+            "2" => Dir::Left,
+            "3" => Dir::Up,
+            _ => return Err(anyhow!("last color digit invalid in {}", self.color)),
+        };
+        Ok((dir, amount))
     }
 }
 
-fn print_grid(grid: &Vec<Vec<char>>) {
-    for r in 0..grid.len() {
-        for c in 0..grid[0].len() {
-            print!("{}", grid[r][c]);
-        }
-        println!();
-    }
-}
-fn count_interior(grid: &Vec<Vec<char>>) -> usize {
-    let rows = grid.len();
-    let cols = grid[0].len();
-    let mut vis = vec![vec![false; cols]; rows];
-    let mut q = VecDeque::new();
-    let mut outer = 0;
-    let s: (i128, i128) = (0, 0);
-    q.push_back(s);
-    vis[0][0] = true;
-    outer += 1;
-    let dr = vec![-1, 0, 1, 0];
-    let dc = vec![0, 1, 0, -1];
-    while let Some(p) = q.pop_front() {
-        for i in 0..4 {
-            let np = (p.0 + dr[i], p.1 + dc[i]);
-            let (r, c) = np;
-            if r >= 0
-                && c >= 0
-                && r < rows as i128
-                && c < cols as i128
-                && !vis[r as usize][c as usize]
-                && grid[r as usize][c as usize] != '#'
-            {
-                q.push_back((r, c));
-                vis[r as usize][c as usize] = true;
-                outer += 1;
-            }
-        }
-    }
-    rows * cols - outer
-}
-
 fn parse_input(input: &str) -> Result<Input> {
     let mut dig_plan = Vec::new();
     for line in input.lines() {
-        let split: Vec<&str> = line.split(" ").collect();
-        assert_eq!(split.len(), 3);
-        let dir = split[0].chars().last().unwrap();
-        let amount = split[1].parse().unwrap();
-        let color = String::from(split[2]);
-        dig_plan.push(DigInstr { dir, amount, color })
+        let (dir, amount) = parse_move(line)?;
+        let color = line
+            .split_whitespace()
+            .nth(2)
+            .ok_or_else(|| anyhow!("no color in {}", line))?;
+        dig_plan.push(DigInstr {
+            dir,
+            amount,
+            color: String::from(color),
+        })
     }
     Ok(Input { dig_plan })
 }
 
-fn solve_one(input: &Input) -> Result<Answer> {
-    let Input { dig_plan } = input;
-    let grid = DigGrid::new(dig_plan);
-    let filled = grid.get_filled_grid();
-    print_grid(&filled);
-    let count = count_interior(&filled);
-    Ok(Answer::Num(count as i128))
-}
-
-struct Dig {
-    hori_lines: Vec<DigLine>,
-    vert_lines: Vec<DigLine>,
+fn lagoon_size(moves: Vec<(Dir, i128)>) -> i128 {
+    let polygon = polygon_from_moves(moves);
+    trace!("lagoon corners {:?}", polygon);
+    geometry::covered_points(&polygon)
 }
 
-impl Dig {
-    fn new(dig_plan: &Vec<DigInstr>) -> Self {
-        let mut new_instr = Vec::new();
-        for DigInstr {
-            dir: _,
-            amount: _,
-            color,
-        } in dig_plan.iter()
-        {
-            let amount = i128::from_str_radix(&color[2..7], 16).unwrap();
-            let dir = match &color[7..8] {
-                "0" => 'R',
-                "1" => 'D',
-                "2" => 'L',
-                "3" => 'U',
-                _ => panic!("last color digit invalid"),
-            };
-            // println!("{} {}", dir, amount);
-            new_instr.push((dir, amount));
-        }
-
-        let mut hori_lines = Vec::new();
-        let mut vert_lines = Vec::new();
-        let mut mapper = HashMap::new();
-        mapper.insert('U', (-1, 0, true));
-        mapper.insert('R', (0, 1, false));
-        mapper.insert('D', (1, 0, true));
-        mapper.insert('L', (0, -1, false));
-        let mut pos = (0, 0);
-        for (dir, amount) in new_instr.iter() {
-            let delta = mapper.get(dir).unwrap();
-            let start = pos;
-            let end = (pos.0 + delta.0 * amount, pos.1 + delta.1 * amount);
-            pos = end;
-            if delta.2 {
-                vert_lines.push(DigLine { start, end });
-            } else {
-                hori_lines.push(DigLine { start, end });
-            }
-        }
-        vert_lines.sort_by(|a, b| {
-            assert_eq!(a.start.1, a.end.1);
-            assert_eq!(b.start.1, b.end.1);
-            a.start.1.cmp(&b.start.1)
-        });
-        Dig {
-            hori_lines,
-            vert_lines,
-        }
-    }
-    // fn new_old(dig_plan: &Vec<DigInstr>) -> Self {
-    //     let new_instr: Vec<(char, i128)> = dig_plan
-    //         .iter()
-    //         .map(|dp| (dp.dir, dp.amount as i128))
-    //         .collect();
-    //     let mut hori_lines = Vec::new();
-    //     let mut vert_lines = Vec::new();
-    //     let mut mapper = HashMap::new();
-    //     mapper.insert('U', (-1, 0, true));
-    //     mapper.insert('R', (0, 1, false));
-    //     mapper.insert('D', (1, 0, true));
-    //     mapper.insert('L', (0, -1, false));
-    //     let mut pos = (0, 0);
-    //     for (dir, amount) in new_instr.iter() {
-    //         let delta = mapper.get(dir).unwrap();
-    //         let start = pos;
-    //         let end = (pos.0 + delta.0 * amount, pos.1 + delta.1 * amount);
-    //         pos = end;
-    //         if delta.2 {
-    //             vert_lines.push(DigLine { start, end });
-    //         } else {
-    //             hori_lines.push(DigLine { start, end });
-    //         }
-    //     }
-    //     vert_lines.sort_by(|a, b| {
-    //         assert_eq!(a.start.1, a.end.1);
-    //         assert_eq!(b.start.1, b.end.1);
-    //         a.start.1.cmp(&b.start.1)
-    //     });
-    //     Dig {
-    //         hori_lines,
-    //         vert_lines,
-    //     }
-    // }
-    fn is_between(r: i128, s: i128, e: i128) -> bool {
-        let mut s = s;
-        let mut e = e;
-        if e < s {
-            std::mem::swap(&mut s, &mut e);
-        }
-        s <= r && r <= e
-    }
-    fn count_interior(&self) -> i128 {
-        let mut min_r = i128::MAX;
-        let mut min_c = i128::MAX;
-        let mut max_r = i128::MIN;
-        let mut max_c = i128::MIN;
-        let mut horimap: HashMap<i128, Vec<(i128, i128)>> = HashMap::new();
-        for DigLine { start, end } in self.hori_lines.iter() {
-            let (r, c) = start;
-            min_r = std::cmp::min(min_r, *r);
-            min_c = std::cmp::min(min_c, *c);
-            max_r = std::cmp::max(max_r, *r);
-            max_c = std::cmp::max(max_c, *c);
-            let mut sc = start.1;
-            let mut ec = end.1;
-            if ec < sc {
-                std::mem::swap(&mut sc, &mut ec);
-            }
-            horimap
-                .entry(*r)
-                .and_modify(|e| e.push((sc, ec)))
-                .or_insert(vec![(sc, ec)]);
-        }
-        for DigLine { start, end: _ } in self.vert_lines.iter() {
-            let (r, c) = start;
-            min_r = std::cmp::min(min_r, *r);
-            min_c = std::cmp::min(min_c, *c);
-            max_r = std::cmp::max(max_r, *r);
-            max_c = std::cmp::max(max_c, *c);
-        }
-        for hl in self.hori_lines.iter() {
-            println!("hori {:?} -> {:?}", hl.start, hl.end);
-        }
-        for vl in self.vert_lines.iter() {
-            println!("vert {:?} -> {:?}", vl.start, vl.end);
-        }
-        let mut total = 0;
-        for r in min_r..=max_r {
-            // println!("--- row {} ---", r);
-            let mut inner = 0;
-
-            // // line on row => just add fully
-            // for hl in self.hori_lines.iter() {
-            //     if hl.start.0 == r {
-            //         inner += i128::abs(hl.start.1 - hl.end.1) + 1;
-            //     }
-            // }
-
-            // #######
-            // #     #
-            // # ### #
-            // # # # #
-            // ### ###
-            // line intersects row => add delta to last intersection => lines have to be sorted by column ascending
-            let intersecting_lines = self
-                .vert_lines
-                .iter()
-                .filter(|l| Self::is_between(r, l.start.0, l.end.0))
-                .collect::<Vec<_>>();
-            // assert!(intersecting_lines.len() % 2 == 0);
-
-            let mut hlines = vec![];
-            if let Some(l) = horimap.get(&r) {
-                hlines = l.clone();
-            }
-            // println!("hlines {:?}", hlines);
-            let mut last_inside = false;
-            let mut last_added = false;
-            for a in intersecting_lines.windows(2) {
-                let left = a[0];
-                let mut ls = left.start.0;
-                let mut le = left.end.0;
-                if le < ls {
-                    std::mem::swap(&mut ls, &mut le);
-                }
-                assert_eq!(left.start.1, left.end.1);
-                let lc = left.start.1;
-                let right = a[1];
-                let mut rs = right.start.0;
-                let mut re = right.end.0;
-                if re < rs {
-                    std::mem::swap(&mut rs, &mut re);
-                }
-                assert_eq!(right.start.1, right.end.1);
-                let rc = right.start.1;
-                assert!(lc < rc);
-                // println!(
-                //     "left ({}, {}), {}; right ({}, {}), {};",
-                //     ls, le, lc, rs, re, rc
-                // );
-                if hlines.contains(&(lc, rc)) {
-                    // println!("line contained");
-                    inner += rc - lc + 1;
-                    if last_added {
-                        inner -= 1;
-                    }
-                    last_added = true;
-                    if ls == re || le == rs {
-                        // crossing
-                        // println!("crossing");
-                        //last_inside = !last_inside
-                    } else if ls == rs || le == re {
-                        // bend
-                        // println!("bend");
-                        last_inside = !last_inside;
-                    } else {
-                        panic!("line connects weirdly");
-                    }
-                } else {
-                    if !last_inside {
-                        inner += rc - lc + 1;
-                        if last_added {
-                            inner -= 1;
-                        }
-                        last_added = true;
-                    } else {
-                        last_added = false;
-                    }
-                    last_inside = !last_inside;
-                }
-            }
-            // println!("--- +{} ---", inner);
-            total += inner;
-        }
-        total
-    }
-}
-struct DigLine {
-    start: (i128, i128),
-    end: (i128, i128),
+fn solve_one(input: &Input) -> Result<Answer> {
+    let Input { dig_plan } = input;
+    let moves = dig_plan.iter().map(|d| (d.dir, d.amount)).collect();
+    Ok(Answer::Num(lagoon_size(moves)))
 }
 
 fn solve_two(input: &Input) -> Result<Answer> {
     let Input { dig_plan } = input;
-    let dig = Dig::new(dig_plan);
-    // let dig = Dig::new_old(dig_plan);
-    let count = dig.count_interior();
-    Ok(Answer::Num(count))
+    let moves = dig_plan
+        .iter()
+        .map(|d| d.decode_color())
+        .collect::<Result<_>>()?;
+    Ok(Answer::Num(lagoon_size(moves)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::common::prop::{self, Generator, Rng};
+    use std::collections::{HashSet, VecDeque};
     use std::sync::LazyLock;
     use test::Bencher;
 
-    static TEST: LazyLock<String> = local_file!("test.txt");
-    static INPUT: LazyLock<String> = local_file!("input.txt");
+    static TEST: LazyLock<String> = local_file!("test");
+    static INPUT: LazyLock<String> = local_file!("input");
 
     #[test]
     fn test_one() -> Result<()> {
@@ -407,6 +116,200 @@ mod tests {
         Ok(())
     }
 
+    /// the grid based part one from before `geometry`: digs every cell of the trench and
+    /// flood fills the outside, slow but simple enough to check `lagoon_size` against
+    struct DigGrid {
+        grid: HashSet<(i128, i128)>,
+    }
+
+    impl DigGrid {
+        fn new(moves: &[(Dir, i128)]) -> Self {
+            let mut grid = HashSet::new();
+            let mut pos = (0, 0);
+            for &(dir, amount) in moves.iter() {
+                let delta = dir.delta();
+                for _ in 0..amount {
+                    pos = (pos.0 + delta.0, pos.1 + delta.1);
+                    grid.insert(pos);
+                }
+            }
+            DigGrid { grid }
+        }
+        fn get_filled_grid(&self) -> Vec<Vec<char>> {
+            let min_x = self.grid.iter().map(|p| p.0).min().unwrap_or(0) - 1;
+            let min_y = self.grid.iter().map(|p| p.1).min().unwrap_or(0) - 1;
+            let max_x = self.grid.iter().map(|p| p.0).max().unwrap_or(0) + 1;
+            let max_y = self.grid.iter().map(|p| p.1).max().unwrap_or(0) + 1;
+            let rows = max_y - min_y + 1;
+            let cols = max_x - min_x + 1;
+            let mut filled = vec![vec!['.'; cols as usize]; rows as usize];
+            for (x, y) in self.grid.iter() {
+                filled[(y - min_y) as usize][(x - min_x) as usize] = '#';
+            }
+            filled
+        }
+    }
+
+    /// everything the flood fill from the empty corner does not reach is dug out
+    fn count_interior(grid: &[Vec<char>]) -> usize {
+        let rows = grid.len();
+        let cols = grid[0].len();
+        let mut vis = vec![vec![false; cols]; rows];
+        let mut q = VecDeque::from([(0, 0)]);
+        vis[0][0] = true;
+        let mut outer = 1;
+        while let Some((r, c)) = q.pop_front() {
+            for (dr, dc) in [(-1, 0), (0, 1), (1, 0), (0, -1)] {
+                let (r, c): (i128, i128) = (r + dr, c + dc);
+                if r >= 0
+                    && c >= 0
+                    && r < rows as i128
+                    && c < cols as i128
+                    && !vis[r as usize][c as usize]
+                    && grid[r as usize][c as usize] != '#'
+                {
+                    q.push_back((r, c));
+                    vis[r as usize][c as usize] = true;
+                    outer += 1;
+                }
+            }
+        }
+        rows * cols - outer
+    }
+
+    fn grid_lagoon_size(moves: &[(Dir, i128)]) -> i128 {
+        count_interior(&DigGrid::new(moves).get_filled_grid()) as i128
+    }
+
+    #[test]
+    fn grid_reference_agrees() -> Result<()> {
+        for input in [&TEST, &INPUT] {
+            let moves: Vec<(Dir, i128)> = parse_input(input)?
+                .dig_plan
+                .iter()
+                .map(|d| (d.dir, d.amount))
+                .collect();
+            assert_eq!(lagoon_size(moves.clone()), grid_lagoon_size(&moves));
+        }
+        Ok(())
+    }
+
+    /// one column of an x-monotone lagoon, `bottom < top`
+    #[derive(Debug, Clone)]
+    struct Column {
+        width: i128,
+        bottom: i128,
+        top: i128,
+    }
+
+    /// lagoons of up to 5 columns, neighbouring columns overlap so the trench never touches itself
+    struct Lagoons;
+
+    fn is_simple(columns: &[Column]) -> bool {
+        !columns.is_empty()
+            && columns.iter().all(|c| c.width > 0 && c.bottom < c.top)
+            && columns
+                .windows(2)
+                .all(|w| w[0].bottom < w[1].top && w[1].bottom < w[0].top)
+    }
+
+    impl Generator for Lagoons {
+        type Value = Vec<Column>;
+        fn generate(&self, rng: &mut Rng) -> Vec<Column> {
+            let bottom = rng.between(-5, 4);
+            let mut columns = vec![Column {
+                width: rng.between(1, 4),
+                bottom,
+                top: rng.between(bottom + 1, 5),
+            }];
+            for _ in 0..rng.below(5) {
+                let last = columns.last().unwrap();
+                let bottom = rng.between(-5, last.top - 1);
+                let top = rng.between(bottom.max(last.bottom) + 1, 6);
+                let width = rng.between(1, 4);
+                columns.push(Column { width, bottom, top });
+            }
+            columns
+        }
+        fn shrink(&self, columns: &Vec<Column>) -> Vec<Vec<Column>> {
+            let mut smaller = Vec::new();
+            for i in 0..columns.len() {
+                let mut fewer = columns.clone();
+                fewer.remove(i);
+                smaller.push(fewer);
+                for change in [(-1, 0, 0), (0, 1, 0), (0, 0, -1)] {
+                    let mut changed = columns.clone();
+                    changed[i].width += change.0;
+                    changed[i].bottom += change.1;
+                    changed[i].top += change.2;
+                    smaller.push(changed);
+                }
+            }
+            smaller.retain(|columns| is_simple(columns));
+            smaller
+        }
+    }
+
+    /// clockwise along the tops and back along the bottoms, without empty moves
+    fn moves(columns: &[Column]) -> Vec<(Dir, i128)> {
+        let vertical = |from: i128, to: i128| match to - from {
+            0 => None,
+            d if d > 0 => Some((Dir::Up, d)),
+            d => Some((Dir::Down, -d)),
+        };
+        let mut moves = Vec::new();
+        moves.extend(vertical(columns[0].bottom, columns[0].top));
+        for (i, column) in columns.iter().enumerate() {
+            moves.push((Dir::Right, column.width));
+            let next = columns.get(i + 1).map_or(column.bottom, |c| c.top);
+            moves.extend(vertical(column.top, next));
+        }
+        for (i, column) in columns.iter().enumerate().rev() {
+            moves.push((Dir::Left, column.width));
+            if i > 0 {
+                moves.extend(vertical(column.bottom, columns[i - 1].bottom));
+            }
+        }
+        moves
+    }
+
+    /// the dig plan with the same moves in the colors as well
+    fn dig_plan(moves: &[(Dir, i128)]) -> String {
+        let mut plan = String::new();
+        for &(dir, amount) in moves {
+            let (letter, digit) = match dir {
+                Dir::Right => ('R', 0),
+                Dir::Down => ('D', 1),
+                Dir::Left => ('L', 2),
+                Dir::Up => ('U', 3),
+            };
+            plan += &format!("{} {} (#{:05x}{})\n", letter, amount, amount, digit);
+        }
+        plan
+    }
+
+    #[test]
+    fn lagoon_size_agrees_with_grid() {
+        prop::check(&Lagoons, 300, |columns| {
+            let moves = moves(columns);
+            let plan = dig_plan(&moves);
+            let expected = Answer::Num(grid_lagoon_size(&moves));
+            for (part, solve) in [
+                (1, super::part_one as fn(&str) -> Result<Answer>),
+                (2, super::part_two),
+            ] {
+                let answer = solve(&plan).map_err(|err| format!("{:#}", err))?;
+                if answer != expected {
+                    return Err(format!(
+                        "part {}: {} instead of {}\n{}",
+                        part, answer, expected, plan
+                    ));
+                }
+            }
+            Ok(())
+        });
+    }
+
     #[bench]
     fn bench_part_one(b: &mut Bencher) {
         b.iter(|| part_one())
@@ -113,22 +113,23 @@ fn solve_one(input: &Input) -> Result<Answer> {
     let mut sum = 0;
     for part in parts.iter() {
         let mut next_wf = String::from("in");
+        let mut path = String::new();
         loop {
             let wf = workflows.get(&next_wf).unwrap();
-            print!("{} -> ", next_wf);
+            path.push_str(&format!("{} -> ", next_wf));
             next_wf = wf.apply(part);
             if next_wf == "A" {
                 sum += part.x + part.m + part.a + part.s;
-                print!("A");
+                path.push('A');
                 break;
             } else if next_wf == "R" {
-                print!("R");
+                path.push('R');
                 break;
             }
         }
-        println!();
+        trace!("{}", path);
     }
-    println!("sum = {}", sum);
+    debug!("sum = {}", sum);
     Ok(Answer::Num(sum))
 }
 
@@ -341,7 +342,7 @@ fn solve_two(input: &Input) -> Result<Answer> {
             }
         }
     }
-    println!("{:?}", graph);
+    trace!("{:?}", graph);
     // build graph -> do bfs while keeping track of current allowed values -> start from back with all A's invert conditions
     // doesn't make sense to take node twice as every node just restricts more! is it really true? path there could be less restrictive?
     let mut q = VecDeque::new();
@@ -369,8 +370,7 @@ fn solve_two(input: &Input) -> Result<Answer> {
             }
         }
     }
-    println!();
-    println!("{:?}", found_ins);
+    trace!("{:?}", found_ins);
     let mut sum = 0;
     for found_in in found_ins.iter() {
         sum += found_in.count();
@@ -380,13 +380,13 @@ fn solve_two(input: &Input) -> Result<Answer> {
         for j in i + 1..fvec.len() {
             let combine = fvec[i].combine(fvec[j]);
             if combine.is_consistent() {
-                println!("{:?} + {:?}", fvec[i], fvec[j]);
-                println!("{:?}", combine);
+                trace!("{:?} + {:?}", fvec[i], fvec[j]);
+                trace!("{:?}", combine);
                 sum -= combine.count();
             }
         }
     }
-    println!("wrong sum {}", sum);
+    debug!("wrong sum {}", sum);
 
     // TODO solve the overlapping ranges problem maybe some day
     // (1,3) (2,5)
@@ -408,8 +408,8 @@ mod tests {
     use std::sync::LazyLock;
     use test::Bencher;
 
-    static TEST: LazyLock<String> = local_file!("test.txt");
-    static INPUT: LazyLock<String> = local_file!("input.txt");
+    static TEST: LazyLock<String> = local_file!("test");
+    static INPUT: LazyLock<String> = local_file!("input");
 
     #[test]
     fn test_one() -> Result<()> {
@@ -208,7 +208,7 @@ impl Conjunction {
                 _ => {}
             }
         }
-        println!("{} all set on iter {}", self.name, i);
+        debug!("{} all set on iter {}", self.name, i);
     }
 }
 
@@ -283,7 +283,7 @@ fn parse_input(input: &str) -> Result<Input> {
                     _ => {}
                 }
             } else {
-                println!("untyped module {}", output);
+                debug!("untyped module {}", output);
             }
         }
     }
@@ -351,13 +351,13 @@ fn solve_one(input: &Input) -> Result<Answer> {
     let (low, high) = push_button(&mut modules);
     total_low += low;
     total_high += high;
-    println!("low={}, high={}", low, high);
+    debug!("low={}, high={}", low, high);
     for _ in 0..999 {
         let (low, high) = push_button(&mut modules);
         total_low += low;
         total_high += high;
     }
-    println!("tlow={}, thigh={}", total_low, total_high);
+    debug!("tlow={}, thigh={}", total_low, total_high);
     Ok(Answer::Num((total_low * total_high) as i128))
 }
 
@@ -381,7 +381,7 @@ fn solve_two(input: &Input) -> Result<Answer> {
             }
         }
         if i % 1000000 == 0 {
-            println!("{}", i);
+            trace!("{}", i);
         }
         // add this to not run infinitely
         if i >= 10000 {
@@ -399,8 +399,8 @@ mod tests {
     use std::sync::LazyLock;
     use test::Bencher;
 
-    static TEST: LazyLock<String> = local_file!("test.txt");
-    static INPUT: LazyLock<String> = local_file!("input.txt");
+    static TEST: LazyLock<String> = local_file!("test");
+    static INPUT: LazyLock<String> = local_file!("input");
 
     #[test]
     fn test_one() -> Result<()> {
@@ -10,7 +10,7 @@ pub fn part_one(input: &str) -> Result<Answer> {
 
 pub fn part_two(input: &str) -> Result<Answer> {
     let input = parse_input(input)?;
-    solve_two(&input)
+    solve_two(&input, 26501365)
 }
 
 struct Input {
@@ -90,25 +90,114 @@ fn solve_one(input: &Input, req_steps: i32) -> Result<Answer> {
     Ok(Answer::Num(sum))
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Method {
+    /// few enough steps to just walk the repeated garden
+    Simulation,
+    /// full tiles of both parities plus corner corrections, needs a clear row and column through S
+    Diamond,
+    /// fit a quadratic through three simulated points one period apart
+    Quadratic,
+}
+
+// Every tile repetition in the infinite garden is the same grid, so everything
+// is expressed in terms of the tile geometry derived from the input.
+struct Geometry {
+    size: usize,
+    offset: usize,
+}
+
+impl Geometry {
+    /// Some(..) if the input satisfies the assumptions of the diamond shortcut
+    fn diamond(input: &Input) -> Option<Geometry> {
+        let Input {
+            grid,
+            rows,
+            cols,
+            start,
+        } = input;
+        let size = *rows;
+        let offset = size / 2;
+        if size != *cols || size.is_multiple_of(2) || *start != (offset as i32, offset as i32) {
+            return None;
+        }
+        let clear_row = |r: usize| grid[r].iter().all(|&c| c != '#');
+        let clear_col = |c: usize| grid.iter().all(|row| row[c] != '#');
+        if [0, offset, size - 1]
+            .into_iter()
+            .all(|i| clear_row(i) && clear_col(i))
+        {
+            Some(Geometry { size, offset })
+        } else {
+            None
+        }
+    }
+}
+
+fn count_infinite(input: &Input, steps: usize) -> (usize, Method) {
+    let period = num::integer::lcm(input.rows, input.cols);
+    if let Some(geometry) = Geometry::diamond(input) {
+        if steps >= geometry.offset && (steps - geometry.offset).is_multiple_of(geometry.size) {
+            return (count_diamond(input, &geometry, steps), Method::Diamond);
+        }
+    }
+    // the reachable area only grows quadratically once the frontier has left
+    // the first couple of tiles, so move the samples out until the second
+    // difference has settled
+    let mut first = steps % period + 2 * period;
+    loop {
+        let samples = [0, 1, 2, 3].map(|k| first + k * period);
+        if steps <= samples[3] {
+            let dist = fill_repeated(input, steps);
+            return (count_within(&dist, steps), Method::Simulation);
+        }
+        let dist = fill_repeated(input, samples[3]);
+        let [y0, y1, y2, y3] = samples.map(|s| count_within(&dist, s) as i128);
+        let d2 = y2 - 2 * y1 + y0;
+        if y3 - 2 * y2 + y1 == d2 {
+            // Newton forward differences
+            let n = ((steps - first) / period) as i128;
+            let plots = y0 + n * (y1 - y0) + n * (n - 1) / 2 * d2;
+            return (plots as usize, Method::Quadratic);
+        }
+        first += period;
+    }
+}
+
+fn count_diamond(input: &Input, geometry: &Geometry, steps: usize) -> usize {
+    let Geometry { size, offset } = *geometry;
+    let dist = fill_grid(vec![(input.start, 0)], &input.grid, input.rows, input.cols);
+    // tiles alternate their parity because size is odd, a cell with distance v
+    // inside a tile d tiles away is reached iff (v + d) has the parity of steps
+    let mut full = [0; 2];
+    let mut corners = [0; 2];
+    for &v in dist.iter().flatten().filter(|&&v| v >= 0) {
+        let parity = v as usize % 2;
+        full[parity] += 1;
+        if v as usize > offset {
+            corners[parity] += 1;
+        }
+    }
+    let n = (steps - offset) / size;
+    let outer = (steps + n) % 2;
+    let inner = 1 - outer;
+    (n + 1) * (n + 1) * full[outer] + n * n * full[inner] - (n + 1) * corners[outer]
+        + n * corners[inner]
+}
+
 fn fill_grid(
     starts: Vec<((i32, i32), i32)>,
-    grid: &Vec<Vec<char>>,
+    grid: &[Vec<char>],
     rows: usize,
     cols: usize,
-    req_steps: i32,
-) -> Option<Vec<Vec<i32>>> {
-    let dr = vec![-1, 0, 1, 0];
-    let dc = vec![0, 1, 0, -1];
+) -> Vec<Vec<i32>> {
+    let dr = [-1, 0, 1, 0];
+    let dc = [0, 1, 0, -1];
     let mut reach_grid: Vec<Vec<i32>> = vec![vec![-1; cols]; rows];
     let mut q = VecDeque::new();
     for (start, steps) in starts {
-        if steps <= req_steps {
-            reach_grid[start.0 as usize][start.1 as usize] = steps;
-            q.push_back((start, steps));
-        }
-    }
-    if q.len() == 0 {
-        return None;
+        reach_grid[start.0 as usize][start.1 as usize] = steps;
+        q.push_back((start, steps));
     }
     while let Some((pos, steps)) = q.pop_front() {
         for i in 0..4 {
@@ -124,72 +213,42 @@ fn fill_grid(
             }
         }
     }
-    Some(reach_grid)
-}
-
-// fn print_grid(grid: &Vec<Vec<i32>>) {
-//     println!();
-//     for r in 0..grid.len() {
-//         for c in 0..grid[0].len() {
-//             if grid[r][c] == -1 {
-//                 print!("{}", format!("{:>3} ", '#'));
-//             } else {
-//                 print!("{:>3} ", grid[r][c]);
-//             }
-//         }
-//         println!();
-//     }
-// }
-
-fn solve_two(input: &Input) -> Result<Answer> {
+    reach_grid
+}
+
+/// distances on a big enough patch of the infinite garden to walk max_steps
+fn fill_repeated(input: &Input, max_steps: usize) -> Vec<Vec<i32>> {
     let Input {
         grid,
         rows,
         cols,
         start,
     } = input;
+    let reps_r = max_steps / rows + 1;
+    let reps_c = max_steps / cols + 1;
+    let big_rows = (2 * reps_r + 1) * rows;
+    let big_cols = (2 * reps_c + 1) * cols;
+    let big: Vec<Vec<char>> = (0..big_rows)
+        .map(|r| (0..big_cols).map(|c| grid[r % rows][c % cols]).collect())
+        .collect();
+    let big_start = (
+        start.0 + (reps_r * rows) as i32,
+        start.1 + (reps_c * cols) as i32,
+    );
+    fill_grid(vec![(big_start, 0)], &big, big_rows, big_cols)
+}
 
-    let req_steps: i32 = 26501365;
-    let initial = fill_grid(vec![(*start, 0)], grid, *rows, *cols, req_steps).unwrap();
-    // print_grid(&initial);
-    let mut even_corners = 0_usize;
-    let mut odd_corners = 0_usize;
-    let mut even_full = 0_usize;
-    let mut odd_full = 0_usize;
-    let mut hash_count = 0_usize;
-    for r in 0..*rows {
-        for c in 0..*cols {
-            let v = initial[r][c];
-            if v == -1 {
-                hash_count += 1;
-                continue;
-            }
-            if v % 2 == 0 {
-                even_full += 1;
-                if v > 65 {
-                    even_corners += 1;
-                }
-            } else {
-                odd_full += 1;
-                if v > 65 {
-                    odd_corners += 1;
-                }
-            }
-        }
-    }
-    assert_eq!(hash_count + even_full + odd_full, 131 * 131);
-    // println!("even corners {}", even_corners);
-    // println!("odd corners {}", odd_corners);
-    // println!("even {}", even_full);
-    // println!("odd {}", odd_full);
-    let n = 202300; // (26501365 - 65) / 131
-    let a = ((n + 1) * (n + 1)) * odd_full;
-    let b = (n * n) * even_full;
-    let c = (n + 1) * odd_corners;
-    let d = n * even_corners;
-    let e = a - c;
-    let ans = e + b + d;
-    Ok(Answer::Num(ans as i128))
+fn count_within(dist: &[Vec<i32>], steps: usize) -> usize {
+    dist.iter()
+        .flatten()
+        .filter(|&&v| v >= 0 && v as usize <= steps && v as usize % 2 == steps % 2)
+        .count()
+}
+
+fn solve_two(input: &Input, steps: usize) -> Result<Answer> {
+    let (plots, method) = count_infinite(input, steps);
+    debug!("{} steps counted with {:?} method", steps, method);
+    Ok(Answer::Num(plots as i128))
 }
 
 #[cfg(test)]
@@ -198,8 +257,8 @@ mod tests {
     use std::sync::LazyLock;
     use test::Bencher;
 
-    static TEST: LazyLock<String> = local_file!("test.txt");
-    static INPUT: LazyLock<String> = local_file!("input.txt");
+    static TEST: LazyLock<String> = local_file!("test");
+    static INPUT: LazyLock<String> = local_file!("input");
 
     #[test]
     fn test_solve_one() -> Result<()> {
@@ -214,24 +273,39 @@ mod tests {
         assert_eq!(answer, Answer::Num(3743));
         Ok(())
     }
-    // TODO: make the algorithm also work correctly for test.txt
-    // In exactly 6 steps, he can still reach 16 garden plots.
-    // In exactly 10 steps, he can reach any of 50 garden plots.
-    // In exactly 50 steps, he can reach 1594 garden plots.
-    // In exactly 100 steps, he can reach 6536 garden plots.
-    // In exactly 500 steps, he can reach 167004 garden plots.
-    // In exactly 1000 steps, he can reach 668697 garden plots.
-    // In exactly 5000 steps, he can reach 16733044 garden plots.
-    // #[test]
-    // fn test_two() -> Result<()> {
-    //     let answer = super::part_two(&TEST)?;
-    //     assert_eq!(answer, Answer::Num(-1));
-    //     Ok(())
-    // }
+    #[test]
+    fn test_two() -> Result<()> {
+        let input = parse_input(&TEST)?;
+        let expected = [
+            (6, 16, Method::Simulation),
+            (10, 50, Method::Simulation),
+            (50, 1594, Method::Simulation),
+            (100, 6536, Method::Quadratic),
+            (500, 167004, Method::Quadratic),
+            (1000, 668697, Method::Quadratic),
+            (5000, 16733044, Method::Quadratic),
+        ];
+        for (steps, plots, method) in expected {
+            assert_eq!(
+                count_infinite(&input, steps),
+                (plots, method),
+                "{steps} steps"
+            );
+        }
+        Ok(())
+    }
     #[test]
     fn part_two() -> Result<()> {
         let answer = super::part_two(&INPUT)?;
         assert_eq!(answer, Answer::Num(618261433219147));
+        let input = parse_input(&INPUT)?;
+        assert_eq!(count_infinite(&input, 26501365).1, Method::Diamond);
+        for n in 1..=4 {
+            let steps = 65 + n * 131;
+            let dist = fill_repeated(&input, steps);
+            let diamond = count_infinite(&input, steps);
+            assert_eq!(diamond, (count_within(&dist, steps), Method::Diamond));
+        }
         Ok(())
     }
 
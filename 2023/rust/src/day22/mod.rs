@@ -1,5 +1,5 @@
-use std::collections::{HashMap, VecDeque};
-
+use super::common::dominators::Dominators;
+use super::common::gen::{InputGenerator, Rng};
 use super::common::*;
 use anyhow::Result;
 use itertools::Itertools;
@@ -57,93 +57,109 @@ struct Input {
     bricks: Vec<Brick>,
 }
 
+/// `size` bricks of length 1 to 4 on the 10 x 10 footprint of the puzzle, each one at a height
+/// of its own so none of them overlap before they fall
+pub struct Bricks;
+
+impl InputGenerator for Bricks {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String {
+        let mut lines = Vec::with_capacity(size);
+        let mut z = 1;
+        for _ in 0..size {
+            let length = rng.between(0, 3);
+            let (mut x, mut y) = (rng.between(0, 9), rng.between(0, 9));
+            let end = match rng.below(3) {
+                0 => {
+                    x = x.min(9 - length);
+                    (x + length, y, z)
+                }
+                1 => {
+                    y = y.min(9 - length);
+                    (x, y + length, z)
+                }
+                _ => (x, y, z + length),
+            };
+            lines.push(format!("{},{},{}~{},{},{}\n", x, y, z, end.0, end.1, end.2));
+            // gaps make the bricks fall, without them the stack is already settled
+            z = end.2 + 1 + if rng.chance(50) { rng.between(0, 5) } else { 0 };
+        }
+        rng.shuffle(&mut lines);
+        lines.concat()
+    }
+}
+
 fn parse_input(input: &str) -> Result<Input> {
     let mut bricks: Vec<Brick> = input.lines().map(|l| Brick::from(l)).collect();
+    // 517 was wrong (too high) because I didn't sort the input by height first...
     bricks.sort_by(|a, b| a.start.2.cmp(&b.start.2));
     Ok(Input { bricks })
 }
 
-fn solve_one(input: &Input) -> Result<Answer> {
-    let Input { bricks } = input;
-    let mut plays: HashMap<(i128, i128), (i128, usize)> = HashMap::new();
-    let mut supporters = vec![0_usize; bricks.len()];
-    let mut supports: Vec<Vec<usize>> = vec![Vec::new(); bricks.len()];
+/// Lets the bricks fall in order of their lowest z onto a height map of the xy plane and
+/// returns the bricks each one comes to rest on, bricks on the ground have none.
+fn settle(bricks: &[Brick]) -> Vec<Vec<usize>> {
+    let width = bricks
+        .iter()
+        .map(|b| b.end.0.max(b.start.0))
+        .max()
+        .unwrap_or(0) as usize
+        + 1;
+    let depth = bricks
+        .iter()
+        .map(|b| b.end.1.max(b.start.1))
+        .max()
+        .unwrap_or(0) as usize
+        + 1;
+    // (height of the top, brick on top) for every column
+    let mut tops: Vec<(i128, Option<usize>)> = vec![(0, None); width * depth];
+    let mut supporters = Vec::with_capacity(bricks.len());
     for (i, brick) in bricks.iter().enumerate() {
-        let xy_cover = brick.xy_cover();
-        let mut max_height = 0;
-        for xy in xy_cover.iter() {
-            if let Some((height, _)) = plays.get(xy) {
-                max_height = std::cmp::max(max_height, *height);
-            }
-        }
-        for xy in xy_cover.iter() {
-            if let Some(&(height, block_idx)) = plays.get(xy) {
-                if height == max_height {
-                    if !supports[block_idx].contains(&i) {
-                        supports[block_idx].push(i);
-                        supporters[i] += 1;
-                    }
-                }
-            }
-            plays.insert(*xy, (max_height + brick.height(), i));
-        }
-    }
-    let mut safe_disintegrate = 0;
-    for i in 0..bricks.len() {
-        if supports[i]
+        let cells: Vec<usize> = brick
+            .xy_cover()
+            .into_iter()
+            .map(|(x, y)| y as usize * width + x as usize)
+            .collect();
+        let rest = cells.iter().map(|&c| tops[c].0).max().unwrap_or(0);
+        let mut below: Vec<usize> = cells
             .iter()
-            .all(|supportee| supporters[*supportee] > 1)
-        {
-            // println!("{} is safe", i);
-            safe_disintegrate += 1;
+            .filter_map(|&c| tops[c].1.filter(|_| tops[c].0 == rest))
+            .collect();
+        below.sort_unstable();
+        below.dedup();
+        for &c in cells.iter() {
+            tops[c] = (rest + brick.height(), Some(i));
         }
+        supporters.push(below);
     }
-    // 517 was wrong (too high) because I didn't sort the input by height first...
-    Ok(Answer::Num(safe_disintegrate))
+    supporters
 }
 
-fn solve_two(input: &Input) -> Result<Answer> {
-    let Input { bricks } = input;
-    let mut plays: HashMap<(i128, i128), (i128, usize)> = HashMap::new();
-    let mut supporters = vec![0_usize; bricks.len()];
-    let mut supports: Vec<Vec<usize>> = vec![Vec::new(); bricks.len()];
-    for (i, brick) in bricks.iter().enumerate() {
-        let xy_cover = brick.xy_cover();
-        let mut max_height = 0;
-        for xy in xy_cover.iter() {
-            if let Some((height, _)) = plays.get(xy) {
-                max_height = std::cmp::max(max_height, *height);
-            }
-        }
-        for xy in xy_cover.iter() {
-            if let Some(&(height, block_idx)) = plays.get(xy) {
-                if height == max_height {
-                    if !supports[block_idx].contains(&i) {
-                        supports[block_idx].push(i);
-                        supporters[i] += 1;
-                    }
-                }
-            }
-            plays.insert(*xy, (max_height + brick.height(), i));
+/// Dominator tree of the support graph rooted at the ground, brick i is vertex i + 1.
+/// Disintegrating a brick makes exactly the bricks it dominates fall.
+fn support_dominators(bricks: &[Brick]) -> Dominators {
+    let mut successors = vec![Vec::new(); bricks.len() + 1];
+    for (i, below) in settle(bricks).iter().enumerate() {
+        if below.is_empty() {
+            successors[0].push(i + 1);
         }
-    }
-    let mut total_falling = 0;
-    for i in 0..bricks.len() {
-        let mut current_supporters = supporters.clone();
-        let mut disintegrate_queue = VecDeque::new();
-        let mut falling = -1; // ignore first disintegration!
-        disintegrate_queue.push_back(i);
-        while let Some(i) = disintegrate_queue.pop_front() {
-            falling += 1;
-            for &supportee in supports[i].iter() {
-                current_supporters[supportee] -= 1;
-                if current_supporters[supportee] == 0 {
-                    disintegrate_queue.push_back(supportee);
-                }
-            }
+        for &b in below.iter() {
+            successors[b + 1].push(i + 1);
         }
-        total_falling += falling;
     }
+    Dominators::new(0, &successors)
+}
+
+fn solve_one(input: &Input) -> Result<Answer> {
+    let Input { bricks } = input;
+    let sizes = support_dominators(bricks).subtree_sizes();
+    let safe_disintegrate = sizes[1..].iter().filter(|&&size| size == 1).count();
+    Ok(Answer::Num(safe_disintegrate as i128))
+}
+
+fn solve_two(input: &Input) -> Result<Answer> {
+    let Input { bricks } = input;
+    let sizes = support_dominators(bricks).subtree_sizes();
+    let total_falling: usize = sizes[1..].iter().map(|size| size - 1).sum();
     Ok(Answer::Num(total_falling as i128))
 }
 
@@ -153,8 +169,8 @@ mod tests {
     use std::sync::LazyLock;
     use test::Bencher;
 
-    static TEST: LazyLock<String> = local_file!("test.txt");
-    static INPUT: LazyLock<String> = local_file!("input.txt");
+    static TEST: LazyLock<String> = local_file!("test");
+    static INPUT: LazyLock<String> = local_file!("input");
 
     #[test]
     fn test_one() -> Result<()> {
@@ -181,6 +197,20 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn generated_stacks_are_solved() -> Result<()> {
+        let input = Bricks.generate(300, &mut Rng::new(7));
+        let bricks = parse_input(&input)?.bricks;
+        assert_eq!(bricks.len(), 300);
+        assert!(bricks.windows(2).all(|w| w[0].end.2 < w[1].start.2));
+        let Answer::Num(safe) = super::part_one(&input)? else {
+            panic!("not a number");
+        };
+        assert!((1..=300).contains(&safe));
+        super::part_two(&input)?;
+        Ok(())
+    }
+
     #[bench]
     fn bench_part_one(b: &mut Bencher) {
         b.iter(|| part_one())
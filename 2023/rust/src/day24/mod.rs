@@ -24,22 +24,20 @@ impl Input {
             for j in i + 1..len {
                 let b = &self.hailstones[j];
                 let intersection = a.intersect_xy(&b);
-                // println!("a = {:?}", a);
-                // println!("b = {:?}", b);
+                trace!("a = {:?}", a);
+                trace!("b = {:?}", b);
                 match intersection {
                     Intersection::None => {
-                        // println!("no intersection");
-                    }
-                    Intersection::PastPoint(_, (_a, _b)) => {
-                        // match (a, b) {
-                        //     (true, true) => println!("intersection in past for both"),
-                        //     (true, false) => println!("intersection in past for A"),
-                        //     (false, true) => println!("intersection in past for B"),
-                        //     _ => panic!("this should never happen, intersection is not in past"),
-                        // }
+                        trace!("no intersection");
                     }
+                    Intersection::PastPoint(_, (past_a, past_b)) => match (past_a, past_b) {
+                        (true, true) => trace!("intersection in past for both"),
+                        (true, false) => trace!("intersection in past for A"),
+                        (false, true) => trace!("intersection in past for B"),
+                        _ => panic!("this should never happen, intersection is not in past"),
+                    },
                     Intersection::Point(p) => {
-                        // println!("intersection at {:?}", p);
+                        trace!("intersection at {:?}", p);
                         if min <= p.0 && p.0 <= max && min <= p.1 && p.1 <= max {
                             crossings += 1;
                         }
@@ -116,17 +114,17 @@ impl Hailstone {
         // use for https://jfmc.github.io/z3-play/
         // (assert (= (+ x (* u t{})) (+ {} (* {} t{})) ))
         //
-        println!(
+        debug!(
             // "x+u*t{} = {}+{}*t{} ;",
             "(assert (= (+ x (* u t{})) (+ {} (* {} t{})) ))",
             time_subscript, self.pos.0, self.delta.0, time_subscript
         );
-        println!(
+        debug!(
             // "y+v*t{} = {}+{}*t{} ;",
             "(assert (= (+ y (* v t{})) (+ {} (* {} t{})) ))",
             time_subscript, self.pos.1, self.delta.1, time_subscript
         );
-        println!(
+        debug!(
             // "z+w*t{} = {}+{}*t{} ;",
             "(assert (= (+ z (* w t{})) (+ {} (* {} t{})) ))",
             time_subscript, self.pos.2, self.delta.2, time_subscript
@@ -183,7 +181,7 @@ fn solve_two(input: &Input) -> Result<Answer> {
             let n1 = a.delta.1 as f64 / b.delta.1 as f64;
             let n2 = a.delta.2 as f64 / b.delta.2 as f64;
             if f64::abs(n0 - n1) < f64::EPSILON && f64::abs(n0 - n2) < f64::EPSILON {
-                println!("found parallel hailstones: {:?}, {:?}", a, b);
+                debug!("found parallel hailstones: {:?}, {:?}", a, b);
             }
         }
     }
@@ -277,8 +275,8 @@ mod tests {
     use std::sync::LazyLock;
     use test::Bencher;
 
-    static TEST: LazyLock<String> = local_file!("test.txt");
-    static INPUT: LazyLock<String> = local_file!("input.txt");
+    static TEST: LazyLock<String> = local_file!("test");
+    static INPUT: LazyLock<String> = local_file!("input");
 
     #[test]
     fn test_solve_one() -> Result<()> {
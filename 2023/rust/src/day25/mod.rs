@@ -55,25 +55,25 @@ fn solve_one(input: &Input) -> Result<Answer> {
     'outer: for s in 0..inodes.len() {
         for t in s + 1..inodes.len() {
             let (_, _, min_cut) = edmonds_karp_sparse(&inodes, &s, &t, edges.clone().into_iter());
-            // println!(
-            //     "{}({}) -> {}({}) = {}",
-            //     nodes[s],
-            //     s,
-            //     nodes[t],
-            //     t,
-            //     min_cut.len()
-            // );
+            trace!(
+                "{}({}) -> {}({}) = {}",
+                nodes[s],
+                s,
+                nodes[t],
+                t,
+                min_cut.len()
+            );
             if min_cut.len() == 3 {
                 let mut new_edges = edges.clone();
-                // println!("{:?}", new_edges.len());
+                trace!("{:?}", new_edges.len());
                 let mut starts: Vec<usize> = Vec::new();
                 for ((cut_a, cut_b), _) in min_cut.iter() {
                     starts.push(*cut_a);
                     starts.push(*cut_b);
-                    // println!(
-                    //     "removing {}({}), {}({})",
-                    //     nodes[*cut_a], *cut_a, nodes[*cut_b], *cut_b
-                    // );
+                    debug!(
+                        "removing {}({}), {}({})",
+                        nodes[*cut_a], *cut_a, nodes[*cut_b], *cut_b
+                    );
                     new_edges = new_edges
                         .into_iter()
                         .filter(|((a, b), _)| {
@@ -81,7 +81,7 @@ fn solve_one(input: &Input) -> Result<Answer> {
                         })
                         .collect();
                 }
-                // println!("{:?}", new_edges.len());
+                trace!("{:?}", new_edges.len());
 
                 let components = connected_components(&inodes, |n| {
                     let neighs: Vec<usize> = new_edges
@@ -91,14 +91,14 @@ fn solve_one(input: &Input) -> Result<Answer> {
                         .collect();
                     neighs
                 });
-                // for com in components.iter() {
-                //     println!("com count {}", com.len());
-                //     for k in com.iter() {
-                //         print!("{}({}), ", nodes[*k], k);
-                //     }
-                //     println!();
-                //     println!("com is {:?}", com);
-                // }
+                for com in components.iter() {
+                    debug!("com count {}", com.len());
+                    let named: Vec<String> = com
+                        .iter()
+                        .map(|k| format!("{}({})", nodes[*k], k))
+                        .collect();
+                    trace!("{}", named.join(", "));
+                }
                 assert_eq!(components.len(), 2);
                 answer = components[0].len() * components[1].len();
                 break 'outer;
@@ -118,8 +118,8 @@ mod tests {
     use std::sync::LazyLock;
     use test::Bencher;
 
-    static TEST: LazyLock<String> = local_file!("test.txt");
-    static INPUT: LazyLock<String> = local_file!("input.txt");
+    static TEST: LazyLock<String> = local_file!("test");
+    static INPUT: LazyLock<String> = local_file!("input");
 
     #[test]
     fn test_one() -> Result<()> {
@@ -1,6 +1,8 @@
 #![feature(test)]
 #![feature(lazy_cell)]
 extern crate test;
+#[macro_use]
+extern crate runner;
 
 mod common;
 mod day00;
@@ -30,21 +32,85 @@ mod day23;
 mod day24;
 mod day25;
 
+use anyhow::{bail, Result};
 use common::*;
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 use structopt::StructOpt;
 
+#[derive(Debug, StructOpt)]
+struct Cli {
+    /// -v shows debug output, -vv also trace output (on stderr)
+    #[structopt(short, long, parse(from_occurrences))]
+    verbose: u64,
+    /// only show diagnostic output of these days, e.g. day09,day12
+    #[structopt(long)]
+    log_day: Option<String>,
+    /// write diagnostic output to this file instead of stderr
+    #[structopt(long, parse(from_os_str))]
+    log_file: Option<PathBuf>,
+    /// text, json (one object per part), csv or table
+    #[structopt(long, default_value = "text")]
+    format: report::Format,
+    /// seconds each part may take before it is reported as TIMEOUT, 0 waits forever
+    #[structopt(long, default_value = "60")]
+    timeout: f64,
+    /// read the input from this file instead of the store, `-` is stdin,
+    /// relative paths start at the project root
+    #[structopt(long, conflicts_with = "variant")]
+    input: Option<String>,
+    /// take this variant from the input store, e.g. test or test2
+    #[structopt(long)]
+    variant: Option<String>,
+    /// print a random input of this size for the day instead of solving it
+    #[structopt(long, conflicts_with_all = &["input", "variant"])]
+    generate: Option<usize>,
+    /// seed of the generated input
+    #[structopt(long, default_value = "0")]
+    seed: u64,
+    #[structopt(subcommand)]
+    opt: Opt,
+}
+
 #[derive(Debug, StructOpt)]
 enum Opt {
     All,
-    Day { day: u8, part: Option<u8> },
-    File { day: u8, part: u8, file: PathBuf },
+    Day {
+        day: u8,
+        part: Option<u8>,
+    },
+    /// deprecated, `--input <file> day <day> <part>` replaces it; relative paths start at the
+    /// current directory here, as they always did
+    File {
+        day: u8,
+        part: u8,
+        file: PathBuf,
+    },
 }
 
-fn main() {
-    let opt = Opt::from_args();
+fn main() -> Result<()> {
+    let cli = Cli::from_args();
+    log::init(
+        log::Level::from_verbosity(cli.verbose),
+        cli.log_day.as_deref(),
+        cli.log_file.as_deref(),
+    )?;
+    let opt = cli.opt;
+    let source = match (cli.input, cli.variant, &opt) {
+        (Some(path), _, Opt::All) => bail!("--input {} only works with a single day", path),
+        (None, None, Opt::File { file, .. }) => {
+            eprintln!(
+                "`file <day> <part> <file>` is deprecated, use `--input <file> day <day> <part>`"
+            );
+            inputs::Source::File(file.clone())
+        }
+        (_, _, Opt::File { .. }) => bail!("`file` already names the input, drop --input/--variant"),
+        (Some(path), _, _) => inputs::Source::from_arg(&path, &project_dir()),
+        (None, Some(variant), _) => inputs::Source::Store(variant),
+        (None, None, _) => inputs::Source::default(),
+    };
 
     let mut solver = Solver::new();
+    solver.timeout = (cli.timeout > 0.0).then(|| Duration::from_secs_f64(cli.timeout));
     solver.add(0, 1, day00::part_one);
     solver.add(0, 2, day00::part_two);
     solver.add(1, 1, day01::part_one);
@@ -97,5 +163,16 @@ fn main() {
     solver.add(24, 2, day24::part_two);
     solver.add(25, 1, day25::part_one);
     solver.add(25, 2, day25::part_two);
-    solver.solve(opt);
+    solver.add_generator(22, day22::Bricks);
+    if let Some(size) = cli.generate {
+        let Opt::Day { day, .. } = opt else {
+            bail!("--generate only works with a single day");
+        };
+        print!("{}", solver.generate(day, size, cli.seed)?);
+        return Ok(());
+    }
+    if !solver.solve(opt, &source, cli.format) {
+        std::process::exit(1);
+    }
+    Ok(())
 }
@@ -0,0 +1,121 @@
+use crate::gen::{self, InputGenerator};
+use crate::guard::{guarded, Guarded};
+use crate::report::{Options, PartResult, Reporter};
+use crate::Answer;
+use anyhow::{bail, Result};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+/// The year all days of this workspace belong to.
+pub const YEAR: u16 = 2024;
+
+/// Root of this workspace, relative `--input` paths start here and not in the working directory.
+pub fn project_dir() -> PathBuf {
+    crate::inputs::canonical(Path::new(env!("CARGO_MANIFEST_DIR")).join(".."))
+}
+
+/// Interface every day library implements.
+///
+/// The day binaries, runners and benchmarks only talk to this, the
+/// parsed input is shared between both parts.
+pub trait Day {
+    const DAY: u8;
+    type Input: Send + Sync + 'static;
+    fn parse(input: &str) -> Result<Self::Input>;
+    fn part_one(input: &Self::Input) -> Result<Answer>;
+    fn part_two(input: &Self::Input) -> Result<Answer>;
+}
+
+type Part<I> = fn(&I) -> Result<Answer>;
+
+/// Reads the puzzle input and prints the answers of both parts,
+/// the logging options of `log::init_from_args` and the runner `Options` are accepted.
+pub fn run<D: Day>() -> Result<()> {
+    let args = crate::log::init_from_args(std::env::args())?;
+    let (options, _) = Options::from_args(args, &project_dir())?;
+    if options.generate.is_some() {
+        bail!("day {:02} has no input generator", D::DAY);
+    }
+    let input = read_input(D::DAY, &options)?;
+    solve_and_report::<D>(&input, &options)?;
+    Ok(())
+}
+
+/// `run` for days with an `InputGenerator`, `--generate <size> [--seed <n>]` prints
+/// a random input instead of solving one.
+pub fn run_with_generator<D: Day + InputGenerator + Default>() -> Result<()> {
+    let args = crate::log::init_from_args(std::env::args())?;
+    let (options, _) = Options::from_args(args, &project_dir())?;
+    if let Some(size) = options.generate {
+        print!("{}", gen::generate(&D::default(), size, options.seed));
+        return Ok(());
+    }
+    let input = read_input(D::DAY, &options)?;
+    solve_and_report::<D>(&input, &options)?;
+    Ok(())
+}
+
+/// Reads the input `options` point to, the store's `input` by default.
+pub fn read_input(day: u8, options: &Options) -> Result<String> {
+    options.source().read(YEAR, day)
+}
+
+/// Parses and solves both parts with timings and reports them in the given format.
+/// Parsing and every part run isolated, a panic or timeout only fails that step.
+/// The parsed input is handed back for days with additional output,
+/// an error is returned if anything failed.
+pub fn solve_and_report<D: Day>(input: &str, options: &Options) -> Result<Arc<D::Input>> {
+    let mut reporter = Reporter::new(options.format);
+    let origin = options.source().origin(YEAR, D::DAY);
+    let owned = input.to_owned();
+    let parsed = guarded(options.timeout, move || {
+        let start = Instant::now();
+        let parsed = D::parse(&owned).map(Arc::new);
+        (parsed, start.elapsed().as_nanos())
+    });
+    let parts: [Part<D::Input>; 2] = [D::part_one, D::part_two];
+    for (part, solve) in (1..).zip(parts) {
+        let mut result = PartResult::new(YEAR, D::DAY, part).with_input(origin.clone());
+        let result = match &parsed {
+            Guarded::Done((Ok(input), parse_ns)) => {
+                let input = Arc::clone(input);
+                let mut result = match guarded(options.timeout, move || {
+                    let start = Instant::now();
+                    let answer = solve(&input);
+                    (answer, start.elapsed().as_nanos())
+                }) {
+                    Guarded::Done((answer, solve_ns)) => {
+                        let mut result = result.with_outcome(&answer);
+                        result.solve_ns = Some(solve_ns);
+                        result
+                    }
+                    Guarded::Panicked(msg) => result.with_panic(&msg),
+                    Guarded::TimedOut => result.with_timeout(options.timeout.unwrap()),
+                };
+                result.parse_ns = Some(*parse_ns);
+                result
+            }
+            Guarded::Done((Err(err), parse_ns)) => {
+                result.error = Some(format!("{:#}", err));
+                result.parse_ns = Some(*parse_ns);
+                result
+            }
+            Guarded::Panicked(msg) => result.with_panic(&format!("parsing: {}", msg)),
+            Guarded::TimedOut => result.with_timeout(options.timeout.unwrap()),
+        };
+        reporter.report(result);
+    }
+    let all_ok = reporter.finish();
+    let parsed = match parsed {
+        Guarded::Done((parsed, _)) => parsed?,
+        Guarded::Panicked(msg) => bail!("parsing day {:02} panicked: {}", D::DAY, msg),
+        Guarded::TimedOut => bail!("parsing day {:02} timed out", D::DAY),
+    };
+    if !all_ok {
+        bail!("day {:02} failed", D::DAY);
+    }
+    Ok(parsed)
+}
@@ -0,0 +1,171 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet},
+    fmt,
+    hash::Hash,
+};
+
+/// Nodes on a cycle that made `toposort` fail, the first node is repeated at the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N: fmt::Debug> fmt::Display for Cycle<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nodes: Vec<String> = self.0.iter().map(|n| format!("{:?}", n)).collect();
+        write!(f, "cycle {}", nodes.join(" -> "))
+    }
+}
+
+impl<N: fmt::Debug> std::error::Error for Cycle<N> {}
+
+/// Kahn's algorithm on the subgraph induced by `nodes`, `successors(a)` are the nodes that must
+/// come after `a`, successors outside of `nodes` are ignored. Whenever several nodes could come
+/// next the one earliest in `nodes` is taken, so already sorted input stays as it is.
+pub fn toposort<N, I>(nodes: &[N], successors: impl Fn(&N) -> I) -> Result<Vec<N>, Cycle<N>>
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut index: HashMap<N, usize> = HashMap::new();
+    let mut unique = Vec::new();
+    for &n in nodes.iter() {
+        if let Entry::Vacant(entry) = index.entry(n) {
+            entry.insert(unique.len());
+            unique.push(n);
+        }
+    }
+    let edges: Vec<Vec<usize>> = unique
+        .iter()
+        .map(|n| {
+            successors(n)
+                .into_iter()
+                .filter_map(|s| index.get(&s).copied())
+                .collect()
+        })
+        .collect();
+    let mut indegree = vec![0; unique.len()];
+    for &s in edges.iter().flatten() {
+        indegree[s] += 1;
+    }
+
+    let mut ready: BinaryHeap<Reverse<usize>> = (0..unique.len())
+        .filter(|&i| indegree[i] == 0)
+        .map(Reverse)
+        .collect();
+    let mut sorted = Vec::with_capacity(unique.len());
+    while let Some(Reverse(i)) = ready.pop() {
+        sorted.push(unique[i]);
+        for &s in edges[i].iter() {
+            indegree[s] -= 1;
+            if indegree[s] == 0 {
+                ready.push(Reverse(s));
+            }
+        }
+    }
+    if sorted.len() == unique.len() {
+        return Ok(sorted);
+    }
+
+    // every node left has a predecessor that is left as well, walking those backwards
+    // has to run into a cycle
+    let mut predecessor = vec![None; unique.len()];
+    for (i, succ) in edges.iter().enumerate() {
+        for &s in succ.iter().filter(|_| indegree[i] > 0) {
+            predecessor[s] = Some(i);
+        }
+    }
+    let mut seen = HashSet::new();
+    let mut current = (0..unique.len()).find(|&i| indegree[i] > 0).unwrap();
+    while seen.insert(current) {
+        current = predecessor[current].unwrap();
+    }
+    let mut cycle = vec![unique[current]];
+    let mut i = predecessor[current].unwrap();
+    while i != current {
+        cycle.push(unique[i]);
+        i = predecessor[i].unwrap();
+    }
+    cycle.push(unique[current]);
+    cycle.reverse();
+    Err(Cycle(cycle))
+}
+
+/// A set of `a` before `b` rules.
+#[derive(Debug, Clone)]
+pub struct Rules<N> {
+    after: HashMap<N, Vec<N>>,
+    pairs: HashSet<(N, N)>,
+}
+
+impl<N: Copy + Eq + Hash> Rules<N> {
+    pub fn new(pairs: impl IntoIterator<Item = (N, N)>) -> Rules<N> {
+        let mut rules = Rules {
+            after: HashMap::new(),
+            pairs: HashSet::new(),
+        };
+        for (before, after) in pairs {
+            if rules.pairs.insert((before, after)) {
+                rules.after.entry(before).or_default().push(after);
+            }
+        }
+        rules
+    }
+    /// nodes that have to come after `n`
+    pub fn successors(&self, n: &N) -> impl Iterator<Item = N> + '_ {
+        self.after.get(n).into_iter().flatten().copied()
+    }
+    /// `Less` if a rule puts `a` first, `Greater` if one puts `b` first, otherwise `Equal`.
+    /// This is only a valid comparator for `sort_by` if the rules order every pair of the
+    /// sorted items, `toposort` works with any acyclic rules.
+    pub fn compare(&self, a: &N, b: &N) -> Ordering {
+        if self.pairs.contains(&(*a, *b)) {
+            Ordering::Less
+        } else if self.pairs.contains(&(*b, *a)) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+    /// no rule is broken by any pair of `items`, not only neighbouring ones
+    pub fn is_sorted(&self, items: &[N]) -> bool {
+        items.iter().enumerate().all(|(i, a)| {
+            items[i + 1..]
+                .iter()
+                .all(|b| self.compare(a, b) != Ordering::Greater)
+        })
+    }
+    pub fn toposort(&self, items: &[N]) -> Result<Vec<N>, Cycle<N>> {
+        toposort(items, |n| self.successors(n))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorts_subsets_stably() {
+        let rules = Rules::new([(47, 53), (97, 13), (97, 61), (75, 29), (61, 13), (29, 13)]);
+        assert_eq!(rules.toposort(&[61, 13, 29]), Ok(vec![61, 29, 13]));
+        assert_eq!(rules.toposort(&[5, 4, 3]), Ok(vec![5, 4, 3]));
+        assert!(rules.is_sorted(&[97, 61, 13]));
+        assert!(!rules.is_sorted(&[13, 5, 97]));
+        let mut pages = vec![13, 61, 97];
+        pages.sort_by(|a, b| rules.compare(a, b));
+        assert_eq!(pages, vec![97, 61, 13]);
+    }
+
+    #[test]
+    fn reports_the_cycle() {
+        let rules = Rules::new([(0, 1), (1, 2), (2, 3), (3, 1), (9, 0)]);
+        let cycle = rules.toposort(&[0, 1, 2, 3]).unwrap_err();
+        assert_eq!(cycle.0.len(), 4);
+        assert_eq!(cycle.0.first(), cycle.0.last());
+        for pair in cycle.0.windows(2) {
+            assert_eq!(rules.compare(&pair[0], &pair[1]), Ordering::Less);
+        }
+        // the cycle is outside of the subset
+        assert_eq!(rules.toposort(&[0, 1, 2]), Ok(vec![0, 1, 2]));
+        assert_eq!(cycle.to_string().split(" -> ").count(), 4);
+    }
+}
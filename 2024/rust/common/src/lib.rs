@@ -1,11 +1,15 @@
 mod answer;
+mod day;
+pub mod graph;
 pub mod point;
 
 #[cfg(feature = "test-utils")]
 pub mod test_utils;
 
 pub use answer::Answer;
+pub use day::{project_dir, read_input, run, run_with_generator, solve_and_report, Day, YEAR};
 pub use regex;
+pub use runner::{debug, gen, guard, inputs, log, report, trace};
 
 #[macro_export]
 macro_rules! regx {
@@ -1,16 +1,49 @@
-use std::fs;
-pub fn read_from_file(filename: &str) -> String {
-    println!("reading {}", filename);
-    let result = fs::read_to_string(filename)
-        .unwrap_or_else(|msg| panic!("error reading {}: {}", filename, msg));
-    result.replace("\r\n", "\n")
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use std::fmt::Display;
+
+pub use examples::examples;
+
+/// `day` is the crate name of the day, e.g. `day14`
+pub fn read_from_store(day: &str, variant: &str) -> String {
+    crate::inputs::read_for_test(crate::YEAR, day, variant)
 }
 
 #[macro_export]
 macro_rules! local_file {
-    ($file:literal) => {
-        LazyLock::new(|| common::test_utils::read_from_file(&format!("{}", $file)))
+    ($variant:literal) => {
+        LazyLock::new(|| common::test_utils::read_from_store(env!("CARGO_PKG_NAME"), $variant))
     };
 }
 // re-export macro such that test_utils::local_file path can be used.
 pub use crate::local_file;
+
+/// The `params` table of an example, for what differs from the real input (e.g. the grid size).
+pub struct Params(serde_json::Map<String, serde_json::Value>);
+
+impl Params {
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<T> {
+        let value = self
+            .0
+            .get(key)
+            .with_context(|| format!("example has no parameter {}", key))?;
+        serde_json::from_value(value.clone()).with_context(|| format!("parameter {}", key))
+    }
+}
+
+/// Body of the tests `examples!` generates, `params` is the example's `params` as json.
+pub fn check_example<A: Display>(
+    day: &str,
+    file: &str,
+    part: u8,
+    params: &str,
+    expected: &str,
+    solve: impl Fn(&str, u8, &Params) -> Result<A>,
+) {
+    let input = read_from_store(day, file);
+    let params = Params(serde_json::from_str(params).unwrap());
+    match solve(&input, part, &params) {
+        Ok(answer) => assert_eq!(answer.to_string(), expected, "part{} of {}", part, file),
+        Err(err) => panic!("part{} of {}: {:#}", part, file, err),
+    }
+}
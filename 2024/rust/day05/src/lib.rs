@@ -0,0 +1,141 @@
+#![feature(test)]
+extern crate test;
+
+use anyhow::{anyhow, Context, Result};
+use common::{graph::Rules, Answer};
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    let input = parse_input(input)?;
+    solve_one(&input)
+}
+
+pub fn part_two(input: &str) -> Result<Answer> {
+    let input = parse_input(input)?;
+    solve_two(&input)
+}
+
+/// Day 05 of Advent of Code 2024
+pub struct Day05;
+
+impl common::Day for Day05 {
+    const DAY: u8 = 5;
+    type Input = Input;
+    fn parse(input: &str) -> Result<Input> {
+        parse_input(input)
+    }
+    fn part_one(input: &Input) -> Result<Answer> {
+        solve_one(input)
+    }
+    fn part_two(input: &Input) -> Result<Answer> {
+        solve_two(input)
+    }
+}
+
+pub struct Input {
+    page_rules: Rules<i128>,
+    page_updates: Vec<Vec<i128>>,
+}
+
+fn get_update_value(update: &[i128]) -> i128 {
+    update[update.len() / 2]
+}
+
+fn parse_input(input: &str) -> Result<Input> {
+    let (rules, updates) = input
+        .split_once("\n\n")
+        .ok_or(anyhow!("just one blank line expected"))?;
+    let page_rules = rules
+        .lines()
+        .map(|line| {
+            let (a, b) = line.split_once("|").ok_or(anyhow!("rule requires |"))?;
+            Ok((a.parse()?, b.parse()?))
+        })
+        .collect::<Result<Vec<(i128, i128)>>>()?;
+    let page_updates = updates
+        .lines()
+        .map(|l| {
+            l.split(",")
+                .map(|s| i128::from_str_radix(s, 10))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<Vec<_>>, _>>()?;
+    Ok(Input {
+        page_rules: Rules::new(page_rules),
+        page_updates,
+    })
+}
+
+fn solve_one(input: &Input) -> Result<Answer> {
+    let Input {
+        page_rules,
+        page_updates,
+    } = input;
+    let sum = page_updates
+        .iter()
+        .filter(|update| page_rules.is_sorted(update))
+        .map(|update| get_update_value(update))
+        .sum();
+    Ok(Answer::Num(sum))
+}
+
+fn solve_two(input: &Input) -> Result<Answer> {
+    let Input {
+        page_rules,
+        page_updates,
+    } = input;
+    let mut sum = 0;
+    for update in page_updates {
+        if page_rules.is_sorted(update) {
+            continue;
+        }
+        let sorted_update = page_rules
+            .toposort(update)
+            .with_context(|| format!("ordering update {:?}", update))?;
+        sum += get_update_value(&sorted_update);
+    }
+    Ok(Answer::Num(sum))
+}
+
+// Quickly obtain answers by running
+// cargo test one [-r]
+// cargo test two [-r]
+#[cfg(test)]
+mod day05_tests {
+    use super::*;
+    use common::test_utils::*;
+    use std::sync::LazyLock;
+    use test::Bencher;
+
+    static INPUT: LazyLock<String> = local_file!("input");
+
+    examples!();
+
+    fn part_one_impl() -> Result<()> {
+        let answer = super::part_one(&INPUT)?;
+        assert_eq!(answer, Answer::Num(6041));
+        Ok(())
+    }
+    #[bench]
+    fn part_one(b: &mut Bencher) {
+        part_one_impl().expect("Error");
+        b.iter(|| part_one_impl())
+    }
+    fn part_two_impl() -> Result<()> {
+        let answer = super::part_two(&INPUT)?;
+        assert_eq!(answer, Answer::Num(4884));
+        Ok(())
+    }
+    #[test]
+    fn cyclic_rules_are_reported() {
+        let err = super::part_two("1|2\n2|3\n3|1\n\n3,2,1\n").unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "ordering update [3, 2, 1]: cycle 3 -> 1 -> 2 -> 3"
+        );
+    }
+    #[bench]
+    fn part_two(b: &mut Bencher) {
+        part_two_impl().expect("Error");
+        b.iter(|| part_two_impl())
+    }
+}
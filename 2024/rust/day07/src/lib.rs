@@ -0,0 +1,239 @@
+#![feature(test)]
+extern crate test;
+
+use anyhow::{anyhow, Result};
+use common::Answer;
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    let input = parse_input(input)?;
+    solve_one(&input)
+}
+
+pub fn part_two(input: &str) -> Result<Answer> {
+    let input = parse_input(input)?;
+    solve_two(&input)
+}
+
+/// Day 07 of Advent of Code 2024
+pub struct Day07;
+
+impl common::Day for Day07 {
+    const DAY: u8 = 7;
+    type Input = Input;
+    fn parse(input: &str) -> Result<Input> {
+        parse_input(input)
+    }
+    fn part_one(input: &Input) -> Result<Answer> {
+        solve_one(input)
+    }
+    fn part_two(input: &Input) -> Result<Answer> {
+        solve_two(input)
+    }
+}
+
+/// A binary operator as data, equations are solved right to left with `undo`. `undo(result, b)`
+/// is every `a` with `apply(a, b) == result`, `Left::Impossible` prunes the branch.
+#[derive(Clone, Copy)]
+pub struct Operator {
+    pub name: &'static str,
+    pub apply: fn(i128, i128) -> Option<i128>,
+    pub undo: fn(i128, i128) -> Left,
+}
+
+/// the left operands that give the result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Left {
+    Impossible,
+    Exactly(i128),
+    /// `a * 0 == 0` holds for any `a`
+    Any,
+}
+
+impl Left {
+    fn when(condition: bool, a: impl FnOnce() -> i128) -> Left {
+        if condition {
+            Left::Exactly(a())
+        } else {
+            Left::Impossible
+        }
+    }
+    pub fn accepts(&self, a: i128) -> bool {
+        match self {
+            Left::Impossible => false,
+            Left::Exactly(left) => *left == a,
+            Left::Any => true,
+        }
+    }
+}
+
+/// 10^(number of decimal digits of n)
+fn digit_shift(n: i128) -> i128 {
+    let mut shift = 10;
+    while shift <= n {
+        shift *= 10;
+    }
+    shift
+}
+
+pub const ADD: Operator = Operator {
+    name: "+",
+    apply: |a, b| a.checked_add(b),
+    undo: |result, b| Left::when(result >= b, || result - b),
+};
+
+pub const MUL: Operator = Operator {
+    name: "*",
+    apply: |a, b| a.checked_mul(b),
+    undo: |result, b| match (result, b) {
+        (0, 0) => Left::Any,
+        (_, 0) => Left::Impossible,
+        _ => Left::when(result % b == 0, || result / b),
+    },
+};
+
+pub const CONCAT: Operator = Operator {
+    name: "||",
+    apply: |a, b| a.checked_mul(digit_shift(b))?.checked_add(b),
+    undo: |result, b| {
+        let shift = digit_shift(b);
+        Left::when(result % shift == b, || result / shift)
+    },
+};
+
+struct Equation {
+    target: i128,
+    nums: Vec<i128>,
+}
+
+impl Equation {
+    fn solvable(&self, operators: &[Operator]) -> bool {
+        !self.nums.is_empty() && Self::solvable_from(self.target, &self.nums, operators)
+    }
+    /// can `nums` be combined left to right into `result`
+    fn solvable_from(result: i128, nums: &[i128], operators: &[Operator]) -> bool {
+        let Some((&last, rest)) = nums.split_last() else {
+            return false;
+        };
+        if rest.is_empty() {
+            return result == last;
+        }
+        operators.iter().any(|op| match (op.undo)(result, last) {
+            Left::Impossible => false,
+            Left::Exactly(left) => Self::solvable_from(left, rest, operators),
+            Left::Any => Self::evaluable(rest[0], &rest[1..], operators),
+        })
+    }
+    /// can `nums` be combined onto `acc` without overflow, the result does not matter
+    fn evaluable(acc: i128, nums: &[i128], operators: &[Operator]) -> bool {
+        let Some((&first, rest)) = nums.split_first() else {
+            return true;
+        };
+        operators.iter().any(|op| {
+            (op.apply)(acc, first).is_some_and(|acc| Self::evaluable(acc, rest, operators))
+        })
+    }
+}
+
+pub struct Input {
+    equations: Vec<Equation>,
+}
+
+fn parse_input(input: &str) -> Result<Input> {
+    let mut equations = Vec::new();
+    for line in input.trim().lines() {
+        let (target, rest) = line.split_once(":").ok_or(anyhow!(": expected"))?;
+        let target = i128::from_str_radix(target.trim(), 10)?;
+        let nums = rest
+            .trim()
+            .split(" ")
+            .map(|n| i128::from_str_radix(n, 10))
+            .collect::<Result<Vec<_>, _>>()?;
+        equations.push(Equation { target, nums });
+    }
+    Ok(Input { equations })
+}
+
+/// sum of the targets that can be reached with `operators`
+pub fn calibration(input: &Input, operators: &[Operator]) -> i128 {
+    input
+        .equations
+        .iter()
+        .filter(|e| e.solvable(operators))
+        .map(|e| e.target)
+        .sum()
+}
+
+fn solve_one(input: &Input) -> Result<Answer> {
+    Ok(Answer::Num(calibration(input, &[ADD, MUL])))
+}
+
+fn solve_two(input: &Input) -> Result<Answer> {
+    Ok(Answer::Num(calibration(input, &[ADD, MUL, CONCAT])))
+}
+
+// Quickly obtain answers by running
+// cargo test one [-r]
+// cargo test two [-r]
+#[cfg(test)]
+mod day07_tests {
+    use super::*;
+    use common::test_utils::*;
+    use std::sync::LazyLock;
+    use test::Bencher;
+
+    static INPUT: LazyLock<String> = local_file!("input");
+
+    examples!();
+
+    fn part_one_impl() -> Result<()> {
+        let answer = super::part_one(&INPUT)?;
+        assert_eq!(answer, Answer::Num(6392012777720));
+        Ok(())
+    }
+    #[bench]
+    fn part_one(b: &mut Bencher) {
+        part_one_impl().expect("Error");
+        b.iter(|| part_one_impl())
+    }
+    fn part_two_impl() -> Result<()> {
+        let answer = super::part_two(&INPUT)?;
+        assert_eq!(answer, Answer::Num(61561126043536));
+        Ok(())
+    }
+    #[test]
+    fn operators_undo_apply() {
+        for op in [ADD, MUL, CONCAT] {
+            for (a, b) in [(156, 6), (48, 6), (17, 8), (1, 10), (0, 5), (7, 0)] {
+                let result = (op.apply)(a, b).unwrap();
+                assert!((op.undo)(result, b).accepts(a), "{} {} {}", a, op.name, b);
+            }
+        }
+        assert_eq!((CONCAT.apply)(15, 6), Some(156));
+        assert_eq!((CONCAT.undo)(156, 7), Left::Impossible);
+        assert_eq!((MUL.undo)(156, 7), Left::Impossible);
+        assert_eq!((MUL.undo)(156, 0), Left::Impossible);
+        assert_eq!((MUL.undo)(0, 0), Left::Any);
+    }
+    #[test]
+    fn zero_products_are_solvable() {
+        // any left side times 0 is 0, as long as it can be computed at all
+        let input = format!(
+            "0: 5 0\n0: 3 4 0\n5: 5 0\n0: {} {} 0\n",
+            i128::MAX,
+            i128::MAX
+        );
+        let solvable: Vec<bool> = parse_input(&input)
+            .unwrap()
+            .equations
+            .iter()
+            .map(|e| e.solvable(&[ADD, MUL]))
+            .collect();
+        assert_eq!(solvable, vec![true, true, true, false]);
+        assert!(!parse_input("5: 5 0").unwrap().equations[0].solvable(&[MUL]));
+    }
+    #[bench]
+    fn part_two(b: &mut Bencher) {
+        part_two_impl().expect("Error");
+        b.iter(|| part_two_impl())
+    }
+}
@@ -0,0 +1,355 @@
+#![feature(test)]
+extern crate test;
+
+use anyhow::{anyhow, Result};
+use common::{regx, Answer};
+use std::{
+    collections::{HashSet, VecDeque},
+    fs,
+    path::Path,
+};
+
+pub fn part_one(input: &str, on_field: (i32, i32)) -> Result<Answer> {
+    let input = parse_input(input)?;
+    solve_one(&input, on_field)
+}
+
+pub fn part_two(input: &str, on_field: (i32, i32)) -> Result<Answer> {
+    let input = parse_input(input)?;
+    solve_two(&input, on_field)
+}
+
+/// Day 14 of Advent of Code 2024
+pub struct Day14;
+
+impl common::Day for Day14 {
+    const DAY: u8 = 14;
+    type Input = Input;
+    fn parse(input: &str) -> Result<Input> {
+        parse_input(input)
+    }
+    fn part_one(input: &Input) -> Result<Answer> {
+        solve_one(input, (101, 103))
+    }
+    fn part_two(input: &Input) -> Result<Answer> {
+        solve_two(input, (101, 103))
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Robot {
+    position: (i32, i32),
+    velocity: (i32, i32),
+}
+
+impl Robot {
+    fn modulo(a: i32, modul: i32) -> i32 {
+        let mut a = a;
+        while a < 0 {
+            a += modul;
+        }
+        while a >= modul {
+            a -= modul;
+        }
+        a
+    }
+    fn move_me(&mut self, times: i32, on_field: (i32, i32)) {
+        self.position = (
+            Self::modulo(self.position.0 + times * self.velocity.0, on_field.0),
+            Self::modulo(self.position.1 + times * self.velocity.1, on_field.1),
+        );
+    }
+    fn safety_factor(robots: &Vec<Robot>, on_field: (i32, i32)) -> usize {
+        let middle = (on_field.0 / 2, on_field.1 / 2);
+        let mut q1 = 0;
+        let mut q2 = 0;
+        let mut q3 = 0;
+        let mut q4 = 0;
+        for robot in robots {
+            let mut x_low = None;
+            if robot.position.0 < middle.0 {
+                x_low = Some(true);
+            } else if robot.position.0 > middle.0 {
+                x_low = Some(false);
+            }
+            let mut y_low = None;
+            if robot.position.1 < middle.1 {
+                y_low = Some(true);
+            } else if robot.position.1 > middle.1 {
+                y_low = Some(false);
+            }
+            match (x_low, y_low) {
+                (None, _) => {}
+                (_, None) => {}
+                (Some(true), Some(true)) => {
+                    q1 += 1;
+                }
+                (Some(false), Some(true)) => {
+                    q2 += 1;
+                }
+                (Some(true), Some(false)) => {
+                    q3 += 1;
+                }
+                (Some(false), Some(false)) => {
+                    q4 += 1;
+                }
+            }
+        }
+        // println!("{} {} {} {}", q1, q2, q3, q4);
+        q1 * q2 * q3 * q4
+    }
+    fn positions(robots: &[Robot]) -> HashSet<(i32, i32)> {
+        robots.iter().map(|r| r.position).collect()
+    }
+}
+
+impl TryFrom<&str> for Robot {
+    type Error = anyhow::Error;
+
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        let robot_regx = regx!(r"p=(-?\d+),(-?\d+) v=(-?\d+),(-?\d+)");
+        let caps = robot_regx
+            .captures(value)
+            .ok_or(anyhow!("robot regex must match"))?;
+        let position = (
+            i32::from_str_radix(&caps[1], 10)?,
+            i32::from_str_radix(&caps[2], 10)?,
+        );
+        let velocity = (
+            i32::from_str_radix(&caps[3], 10)?,
+            i32::from_str_radix(&caps[4], 10)?,
+        );
+        Ok(Robot { position, velocity })
+    }
+}
+
+pub struct Input {
+    robots: Vec<Robot>,
+}
+
+fn parse_input(input: &str) -> Result<Input> {
+    let robots = input
+        .trim()
+        .lines()
+        .map(|l| Robot::try_from(l))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Input { robots })
+}
+
+fn solve_one(input: &Input, on_field: (i32, i32)) -> Result<Answer> {
+    let Input { robots } = input;
+    let mut the_robots = robots.clone();
+    the_robots.iter_mut().for_each(|r| r.move_me(100, on_field));
+    Ok(Answer::Num(
+        Robot::safety_factor(&the_robots, on_field) as i128
+    ))
+}
+
+fn solve_two(input: &Input, on_field: (i32, i32)) -> Result<Answer> {
+    let picture = find_robot_picture(&input.robots, on_field, Detector::Variance)?;
+    Ok(Answer::Num(picture.time as i128))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Detector {
+    /// x and y repeat independently with period width and height, the picture
+    /// is where both coordinates are least spread, combined via CRT
+    Variance,
+    /// the picture is the frame with the biggest clump of adjacent robots
+    LargestComponent,
+}
+
+pub struct Picture {
+    pub time: i32,
+    on_field: (i32, i32),
+    occupied: HashSet<(i32, i32)>,
+}
+
+impl Picture {
+    pub fn render(&self) -> String {
+        let mut frame = String::new();
+        for y in 0..self.on_field.1 {
+            for x in 0..self.on_field.0 {
+                frame.push(if self.occupied.contains(&(x, y)) {
+                    'R'
+                } else {
+                    '.'
+                });
+            }
+            frame.push('\n');
+        }
+        frame
+    }
+    /// plain text PPM, robots are green on black
+    pub fn write_ppm(&self, path: &Path) -> Result<()> {
+        let (width, height) = self.on_field;
+        let mut ppm = format!("P3\n{} {}\n255\n", width, height);
+        for y in 0..height {
+            for x in 0..width {
+                ppm.push_str(if self.occupied.contains(&(x, y)) {
+                    "0 255 0\n"
+                } else {
+                    "0 0 0\n"
+                });
+            }
+        }
+        fs::write(path, ppm)?;
+        Ok(())
+    }
+}
+
+fn find_robot_picture(
+    robots: &[Robot],
+    on_field: (i32, i32),
+    detector: Detector,
+) -> Result<Picture> {
+    let (width, height) = on_field;
+    let time = match detector {
+        Detector::Variance => {
+            let tx = lowest_variance(robots, width, |r| (r.position.0, r.velocity.0));
+            let ty = lowest_variance(robots, height, |r| (r.position.1, r.velocity.1));
+            crt(tx, width, ty, height).ok_or(anyhow!(
+                "field dimensions {:?} must be coprime to combine the periods",
+                on_field
+            ))?
+        }
+        Detector::LargestComponent => (0..width * height)
+            .max_by_key(|&t| {
+                // prefer the earliest time on ties
+                let occupied = Robot::positions(&moved(robots, t, on_field));
+                (largest_component(&occupied), -t)
+            })
+            .ok_or(anyhow!("field must not be empty"))?,
+    };
+    Ok(Picture {
+        time,
+        on_field,
+        occupied: Robot::positions(&moved(robots, time, on_field)),
+    })
+}
+
+/// Finds the time step at which the robots form a picture.
+pub fn find_picture(input: &Input, on_field: (i32, i32), detector: Detector) -> Result<Picture> {
+    find_robot_picture(&input.robots, on_field, detector)
+}
+
+fn moved(robots: &[Robot], times: i32, on_field: (i32, i32)) -> Vec<Robot> {
+    let mut the_robots = robots.to_vec();
+    the_robots
+        .iter_mut()
+        .for_each(|r| r.move_me(times, on_field));
+    the_robots
+}
+
+/// time in 0..period at which the given coordinate of all robots has the lowest variance
+fn lowest_variance(robots: &[Robot], period: i32, coord: impl Fn(&Robot) -> (i32, i32)) -> i32 {
+    let n = robots.len() as i64;
+    (0..period)
+        .min_by_key(|&t| {
+            let values: Vec<i64> = robots
+                .iter()
+                .map(|r| {
+                    let (p, v) = coord(r);
+                    Robot::modulo(p + t * v, period) as i64
+                })
+                .collect();
+            let sum: i64 = values.iter().sum();
+            let sum_sq: i64 = values.iter().map(|v| v * v).sum();
+            // n^2 * variance, stays integral
+            n * sum_sq - sum * sum
+        })
+        .unwrap_or(0)
+}
+
+/// smallest t >= 0 with t = a mod m and t = b mod n
+fn crt(a: i32, m: i32, b: i32, n: i32) -> Option<i32> {
+    (0..n)
+        .map(|k| a + k * m)
+        .find(|t| Robot::modulo(*t, n) == b)
+        .filter(|_| gcd(m, n) == 1)
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn largest_component(occupied: &HashSet<(i32, i32)>) -> usize {
+    let mut seen = HashSet::new();
+    let mut largest = 0;
+    for &start in occupied {
+        if !seen.insert(start) {
+            continue;
+        }
+        let mut size = 0;
+        let mut queue = VecDeque::from([start]);
+        while let Some((x, y)) = queue.pop_front() {
+            size += 1;
+            for neigh in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                if occupied.contains(&neigh) && seen.insert(neigh) {
+                    queue.push_back(neigh);
+                }
+            }
+        }
+        largest = largest.max(size);
+    }
+    largest
+}
+
+// Quickly obtain answers by running
+// cargo test one [-r]
+// cargo test two [-r]
+#[cfg(test)]
+mod day14_tests {
+    use super::*;
+    use common::test_utils::*;
+    use std::sync::LazyLock;
+    use test::Bencher;
+
+    static INPUT: LazyLock<String> = local_file!("input");
+
+    examples!(|input: &str, part: u8, params: &Params| {
+        let field = params.get("field")?;
+        match part {
+            1 => super::part_one(input, field),
+            _ => super::part_two(input, field),
+        }
+    });
+
+    fn part_one_impl() -> Result<()> {
+        let answer = super::part_one(&INPUT, (101, 103))?;
+        assert_eq!(answer, Answer::Num(218619324));
+        Ok(())
+    }
+    #[bench]
+    fn part_one(b: &mut Bencher) {
+        part_one_impl().expect("Error");
+        b.iter(|| part_one_impl())
+    }
+    fn part_two_impl() -> Result<()> {
+        let answer = super::part_two(&INPUT, (101, 103))?;
+        assert_eq!(answer, Answer::Num(6446));
+        Ok(())
+    }
+    #[test]
+    fn detectors_agree() -> Result<()> {
+        let input = parse_input(&INPUT)?;
+        let by_variance = find_robot_picture(&input.robots, (101, 103), Detector::Variance)?;
+        let by_component =
+            find_robot_picture(&input.robots, (101, 103), Detector::LargestComponent)?;
+        assert_eq!(by_variance.time, 6446);
+        assert_eq!(by_component.time, 6446);
+        assert!(by_variance
+            .render()
+            .contains("RRRRRRRRRRRRRRRRRRRRRRRRRRRRRRR"));
+        Ok(())
+    }
+    #[bench]
+    fn part_two(b: &mut Bencher) {
+        part_two_impl().expect("Error");
+        b.iter(|| part_two_impl())
+    }
+}
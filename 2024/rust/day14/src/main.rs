@@ -1,216 +1,33 @@
-#![feature(test)]
-extern crate test;
-
-use anyhow::{anyhow, Result};
-use common::{regx, Answer};
-use std::{collections::HashMap, io};
+use anyhow::{bail, Result};
+use common::{read_input, report::Options, solve_and_report, Day};
+use day14::{find_picture, Day14, Detector};
+use std::{fs, path::Path};
 
+/// usage: day14 [--largest-component] [frame.ppm|frame.txt] [--input <path|->]
+/// the optional file receives the detected picture for visual confirmation
 pub fn main() -> Result<()> {
-    let stdin = io::read_to_string(io::stdin())?;
-    println!("part1: {}", part_one(&stdin, (101, 103))?);
-    println!("part2: {}", part_two(&stdin, (101, 103))?);
-    Ok(())
-}
-
-pub fn part_one(input: &str, on_field: (i32, i32)) -> Result<Answer> {
-    let input = parse_input(input)?;
-    solve_one(&input, on_field)
-}
-
-pub fn part_two(input: &str, on_field: (i32, i32)) -> Result<Answer> {
-    let input = parse_input(input)?;
-    solve_two(&input, on_field)
-}
-
-#[derive(Clone, Copy)]
-struct Robot {
-    position: (i32, i32),
-    velocity: (i32, i32),
-}
-
-impl Robot {
-    fn modulo(a: i32, modul: i32) -> i32 {
-        let mut a = a;
-        while a < 0 {
-            a += modul;
+    let args = common::log::init_from_args(std::env::args())?;
+    let (options, args) = Options::from_args(args, &common::project_dir())?;
+    let mut detector = Detector::Variance;
+    let mut frame_file = None;
+    for arg in args {
+        match arg.as_str() {
+            "--largest-component" => detector = Detector::LargestComponent,
+            option if option.starts_with('-') => bail!("unknown option {}", option),
+            _ if frame_file.is_some() => bail!("only one frame file"),
+            path => frame_file = Some(path.to_owned()),
         }
-        while a >= modul {
-            a -= modul;
-        }
-        a
-    }
-    fn move_me(&mut self, times: i32, on_field: (i32, i32)) {
-        self.position = (
-            Self::modulo(self.position.0 + times * self.velocity.0, on_field.0),
-            Self::modulo(self.position.1 + times * self.velocity.1, on_field.1),
-        );
-    }
-    fn safety_factor(robots: &Vec<Robot>, on_field: (i32, i32)) -> usize {
-        let middle = (on_field.0 / 2, on_field.1 / 2);
-        let mut q1 = 0;
-        let mut q2 = 0;
-        let mut q3 = 0;
-        let mut q4 = 0;
-        for robot in robots {
-            let mut x_low = None;
-            if robot.position.0 < middle.0 {
-                x_low = Some(true);
-            } else if robot.position.0 > middle.0 {
-                x_low = Some(false);
-            }
-            let mut y_low = None;
-            if robot.position.1 < middle.1 {
-                y_low = Some(true);
-            } else if robot.position.1 > middle.1 {
-                y_low = Some(false);
-            }
-            match (x_low, y_low) {
-                (None, _) => {}
-                (_, None) => {}
-                (Some(true), Some(true)) => {
-                    q1 += 1;
-                }
-                (Some(false), Some(true)) => {
-                    q2 += 1;
-                }
-                (Some(true), Some(false)) => {
-                    q3 += 1;
-                }
-                (Some(false), Some(false)) => {
-                    q4 += 1;
-                }
-            }
-        }
-        // println!("{} {} {} {}", q1, q2, q3, q4);
-        q1 * q2 * q3 * q4
-    }
-    fn print_robots(robots: &Vec<Robot>, on_field: (i32, i32)) {
-        let mut robot_map: HashMap<(i32, i32), i32> = HashMap::new();
-        for robot in robots {
-            robot_map
-                .entry(robot.position)
-                .and_modify(|e| *e += 1)
-                .or_insert(1);
-        }
-        for y in 0..on_field.1 {
-            for x in 0..on_field.0 {
-                if robot_map.contains_key(&(x, y)) {
-                    print!("R");
-                } else {
-                    print!(".");
-                }
-            }
-            println!();
-        }
-        println!();
-    }
-}
-
-impl TryFrom<&str> for Robot {
-    type Error = anyhow::Error;
-
-    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
-        let robot_regx = regx!(r"p=(-?\d+),(-?\d+) v=(-?\d+),(-?\d+)");
-        let caps = robot_regx
-            .captures(value)
-            .ok_or(anyhow!("robot regex must match"))?;
-        let position = (
-            i32::from_str_radix(&caps[1], 10)?,
-            i32::from_str_radix(&caps[2], 10)?,
-        );
-        let velocity = (
-            i32::from_str_radix(&caps[3], 10)?,
-            i32::from_str_radix(&caps[4], 10)?,
-        );
-        Ok(Robot { position, velocity })
     }
-}
-
-struct Input {
-    robots: Vec<Robot>,
-}
-
-fn parse_input(input: &str) -> Result<Input> {
-    let robots = input
-        .trim()
-        .lines()
-        .map(|l| Robot::try_from(l))
-        .collect::<Result<Vec<_>, _>>()?;
-    Ok(Input { robots })
-}
-
-fn solve_one(input: &Input, on_field: (i32, i32)) -> Result<Answer> {
-    let Input { robots } = input;
-    let mut the_robots = robots.clone();
-    the_robots.iter_mut().for_each(|r| r.move_me(100, on_field));
-    Ok(Answer::Num(
-        Robot::safety_factor(&the_robots, on_field) as i128
-    ))
-}
-
-fn solve_two(input: &Input, on_field: (i32, i32)) -> Result<Answer> {
-    let Input { robots } = input;
-    let mut the_robots = robots.clone();
-    let mut t = 0;
-    let mut lowest_safety_factor = None;
-    let mut low_t = None;
-    while t < 100_000 {
-        t += 1;
-        the_robots.iter_mut().for_each(|r| r.move_me(1, on_field));
-        let safety = Robot::safety_factor(&the_robots, on_field);
-        if lowest_safety_factor.map_or(true, |f| safety < f) {
-            println!("time {}", t);
-            Robot::print_robots(&the_robots, on_field);
-            lowest_safety_factor = Some(safety);
-            low_t = Some(t);
+    let text = read_input(Day14::DAY, &options)?;
+    let input = solve_and_report::<Day14>(&text, &options)?;
+    if let Some(path) = frame_file {
+        let picture = find_picture(&input, (101, 103), detector)?;
+        let path = Path::new(&path);
+        if path.extension().is_some_and(|e| e == "ppm") {
+            picture.write_ppm(path)?;
+        } else {
+            fs::write(path, picture.render())?;
         }
     }
-    Ok(Answer::Num(low_t.unwrap()))
-}
-
-// Quickly obtain answers by running
-// cargo test one [-r]
-// cargo test two [-r]
-#[cfg(test)]
-mod day14_tests {
-    use super::*;
-    use common::test_utils::*;
-    use std::sync::LazyLock;
-    use test::Bencher;
-
-    static TEST: LazyLock<String> = local_file!("test");
-    static INPUT: LazyLock<String> = local_file!("input");
-
-    #[test]
-    fn test_one() -> Result<()> {
-        let answer = super::part_one(&TEST, (11, 7))?;
-        assert_eq!(answer, Answer::Num(12));
-        Ok(())
-    }
-    fn part_one_impl() -> Result<()> {
-        let answer = super::part_one(&INPUT, (101, 103))?;
-        assert_eq!(answer, Answer::Num(218619324));
-        Ok(())
-    }
-    #[bench]
-    fn part_one(b: &mut Bencher) {
-        part_one_impl().expect("Error");
-        b.iter(|| part_one_impl())
-    }
-    #[test]
-    fn test_two() -> Result<()> {
-        // let answer = super::part_two(&TEST, (11, 7))?;
-        //assert_eq!(answer, Answer::Num(0));
-        Ok(())
-    }
-    fn part_two_impl() -> Result<()> {
-        let answer = super::part_two(&INPUT, (101, 103))?;
-        assert_eq!(answer, Answer::Num(6446));
-        Ok(())
-    }
-    #[bench]
-    fn part_two(b: &mut Bencher) {
-        part_two_impl().expect("Error");
-        b.iter(|| part_two_impl())
-    }
+    Ok(())
 }
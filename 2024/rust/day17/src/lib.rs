@@ -0,0 +1,367 @@
+#![feature(test)]
+extern crate test;
+
+use anyhow::{anyhow, Result};
+use common::Answer;
+use std::fmt;
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    let input = parse_input(input)?;
+    solve_one(&input)
+}
+
+pub fn part_two(input: &str) -> Result<Answer> {
+    let input = parse_input(input)?;
+    solve_two(&input)
+}
+
+/// Day 17 of Advent of Code 2024
+pub struct Day17;
+
+impl common::Day for Day17 {
+    const DAY: u8 = 17;
+    type Input = Input;
+    fn parse(input: &str) -> Result<Input> {
+        parse_input(input)
+    }
+    fn part_one(input: &Input) -> Result<Answer> {
+        solve_one(input)
+    }
+    fn part_two(input: &Input) -> Result<Answer> {
+        solve_two(input)
+    }
+}
+
+pub struct Input {
+    register_a: u128,
+    register_b: u128,
+    register_c: u128,
+    program: Vec<u8>,
+}
+
+pub struct Computer {
+    register_a: u128,
+    register_b: u128,
+    register_c: u128,
+}
+impl Input {
+    pub fn program(&self) -> &[u8] {
+        &self.program
+    }
+}
+impl From<&Input> for Computer {
+    fn from(input: &Input) -> Self {
+        Self {
+            register_a: input.register_a,
+            register_b: input.register_b,
+            register_c: input.register_c,
+        }
+    }
+}
+impl Computer {
+    fn reset(&mut self, register_a: u128, input: &Input) {
+        self.register_a = register_a;
+        self.register_b = input.register_b;
+        self.register_c = input.register_c;
+    }
+    fn output_string(program_output: &[u8]) -> String {
+        program_output
+            .iter()
+            .map(|u| u.to_string())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+    fn mnemonic(opcode: u8) -> Result<&'static str> {
+        Ok(match opcode {
+            0 => "adv",
+            1 => "bxl",
+            2 => "bst",
+            3 => "jnz",
+            4 => "bxc",
+            5 => "out",
+            6 => "bdv",
+            7 => "cdv",
+            _ => Err(anyhow!("invalid opcode {}", opcode))?,
+        })
+    }
+    fn combo_name(operand: u8) -> Result<String> {
+        Ok(match operand {
+            0..=3 => operand.to_string(),
+            4 => String::from("A"),
+            5 => String::from("B"),
+            6 => String::from("C"),
+            _ => Err(anyhow!("combo operand {} is reserved", operand))?,
+        })
+    }
+    /// human readable form of one instruction, e.g. `cdv B`
+    fn instruction(opcode: u8, operand: u8) -> Result<String> {
+        let mnemonic = Self::mnemonic(opcode)?;
+        Ok(match opcode {
+            1 | 3 => format!("{} {}", mnemonic, operand),
+            4 => String::from(mnemonic),
+            _ => format!("{} {}", mnemonic, Self::combo_name(operand)?),
+        })
+    }
+    pub fn disassemble(program: &[u8]) -> Result<Vec<String>> {
+        program
+            .chunks_exact(2)
+            .map(|ins| Self::instruction(ins[0], ins[1]))
+            .collect()
+    }
+    /// executes the instruction at instruction_pointer
+    /// returns the next instruction pointer and the output if there was one
+    fn step(&mut self, program: &[u8], instruction_pointer: usize) -> Result<(usize, Option<u8>)> {
+        let opcode = program[instruction_pointer];
+        let operand = program[instruction_pointer + 1];
+        let mut output = None;
+        match opcode {
+            0 => self.adv(operand),
+            1 => self.bxl(operand),
+            2 => self.bst(operand),
+            3 => {
+                if let Some(new_instruction_pointer) = self.jnz(operand) {
+                    return Ok((new_instruction_pointer, None));
+                }
+            }
+            4 => self.bxc(operand),
+            5 => output = Some(self.out(operand)),
+            6 => self.bdv(operand),
+            7 => self.cdv(operand),
+            _ => return Err(anyhow!("invalid opcode")),
+        }
+        Ok((instruction_pointer + 2, output))
+    }
+    fn run_program(&mut self, program: &[u8]) -> Result<Vec<u8>> {
+        let mut output = Vec::new();
+        let mut instruction_pointer = 0;
+        while instruction_pointer + 1 < program.len() {
+            // +1 because opcode but no operand
+            let (next, out) = self.step(program, instruction_pointer)?;
+            output.extend(out);
+            instruction_pointer = next;
+        }
+        Ok(output)
+    }
+    /// runs the program and records the registers after every instruction
+    pub fn trace(&mut self, program: &[u8]) -> Result<Vec<TraceStep>> {
+        let mut steps = Vec::new();
+        let mut instruction_pointer = 0;
+        while instruction_pointer + 1 < program.len() {
+            let (opcode, operand) = (
+                program[instruction_pointer],
+                program[instruction_pointer + 1],
+            );
+            let (next, output) = self.step(program, instruction_pointer)?;
+            steps.push(TraceStep {
+                instruction_pointer,
+                instruction: Self::instruction(opcode, operand)?,
+                registers: (self.register_a, self.register_b, self.register_c),
+                output,
+            });
+            instruction_pointer = next;
+        }
+        Ok(steps)
+    }
+    fn combo(&self, operand: u8) -> u128 {
+        match operand {
+            4 => self.register_a,
+            5 => self.register_b,
+            6 => self.register_c,
+            x => x as u128,
+        }
+    }
+    fn adv(&mut self, operand: u8) {
+        let numerator = self.register_a;
+        let denominator = 1u128 << self.combo(operand);
+        self.register_a = numerator / denominator;
+    }
+    fn bxl(&mut self, operand: u8) {
+        self.register_b = self.register_b ^ operand as u128;
+    }
+    fn bst(&mut self, operand: u8) {
+        self.register_b = self.combo(operand) % 8;
+    }
+    fn jnz(&mut self, operand: u8) -> Option<usize> {
+        if self.register_a == 0 {
+            return None;
+        }
+        Some(operand as usize)
+    }
+    fn bxc(&mut self, _operand: u8) {
+        self.register_b = self.register_b ^ self.register_c;
+    }
+    fn out(&mut self, operand: u8) -> u8 {
+        (self.combo(operand) % 8) as u8
+    }
+    fn bdv(&mut self, operand: u8) {
+        let numerator = self.register_a;
+        let denominator = 1u128 << self.combo(operand);
+        self.register_b = numerator / denominator;
+    }
+    fn cdv(&mut self, operand: u8) {
+        let numerator = self.register_a;
+        let denominator = 1u128 << self.combo(operand);
+        self.register_c = numerator / denominator;
+    }
+}
+
+pub struct TraceStep {
+    instruction_pointer: usize,
+    instruction: String,
+    registers: (u128, u128, u128),
+    output: Option<u8>,
+}
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (a, b, c) = self.registers;
+        write!(
+            f,
+            "{:>3}: {:<6} A={} B={} C={}",
+            self.instruction_pointer, self.instruction, a, b, c
+        )?;
+        if let Some(output) = self.output {
+            write!(f, " -> {}", output)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_register(register: &str) -> Result<u128> {
+    Ok(u128::from_str_radix(
+        register
+            .split_once(": ")
+            .ok_or(anyhow!("register must be present"))?
+            .1,
+        10,
+    )?)
+}
+
+fn parse_input(input: &str) -> Result<Input> {
+    let (registers, program) = input
+        .trim()
+        .split_once("\n\n")
+        .ok_or(anyhow!("there should be an empty line"))?;
+    let registers: Vec<&str> = registers.lines().collect();
+    let register_a = parse_register(registers[0])?;
+    let register_b = parse_register(registers[1])?;
+    let register_c = parse_register(registers[2])?;
+    let program = program
+        .split_once(": ")
+        .ok_or(anyhow!("program must be present"))?
+        .1
+        .split(",")
+        .map(|n| u8::from_str_radix(n, 10))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Input {
+        register_a,
+        register_b,
+        register_c,
+        program,
+    })
+}
+
+fn solve_one(input: &Input) -> Result<Answer> {
+    let mut computer = Computer::from(input);
+    let program_output = computer.run_program(&input.program)?;
+    let program_output = Computer::output_string(&program_output);
+    Ok(Answer::Str(program_output))
+}
+
+/// Finds the smallest register A for which the program outputs itself.
+/// Every loop iteration has to consume the lowest 3 bits of A (`adv 3`), so A
+/// is built from the last output backwards by prepending 3 bits at a time and
+/// keeping every candidate that reproduces the tail of the program.
+fn find_quine(input: &Input) -> Result<u128> {
+    let program = &input.program;
+    if !Computer::disassemble(program)?.contains(&String::from("adv 3")) {
+        Err(anyhow!(
+            "the solver expects the program to shift A by 3 bits per loop"
+        ))?;
+    }
+    let mut computer = Computer::from(input);
+    let mut candidates = vec![0];
+    for position in (0..program.len()).rev() {
+        let mut next_candidates = Vec::new();
+        for prefix in candidates {
+            for bits in 0..8 {
+                let register_a = prefix << 3 | bits;
+                computer.reset(register_a, input);
+                if computer.run_program(program)? == program[position..] {
+                    next_candidates.push(register_a);
+                }
+            }
+        }
+        candidates = next_candidates;
+    }
+    candidates.sort();
+    for register_a in candidates {
+        computer.reset(register_a, input);
+        if register_a > 0 && computer.run_program(program)? == *program {
+            return Ok(register_a);
+        }
+    }
+    Err(anyhow!(
+        "there is no value for A that turns the program into a quine"
+    ))
+}
+
+fn solve_two(input: &Input) -> Result<Answer> {
+    Ok(Answer::Num(find_quine(input)? as i128))
+}
+
+// Quickly obtain answers by running
+// cargo test one [-r]
+// cargo test two [-r]
+#[cfg(test)]
+mod day17_tests {
+    use super::*;
+    use common::test_utils::*;
+    use std::sync::LazyLock;
+    use test::Bencher;
+
+    static TEST: LazyLock<String> = local_file!("test");
+    static INPUT: LazyLock<String> = local_file!("input");
+
+    examples!();
+
+    fn part_one_impl() -> Result<()> {
+        let answer = super::part_one(&INPUT)?;
+        assert_eq!(answer, Answer::Str(String::from("4,1,5,3,1,5,3,5,7")));
+        Ok(())
+    }
+    #[bench]
+    fn part_one(b: &mut Bencher) {
+        part_one_impl().expect("Error");
+        b.iter(|| part_one_impl())
+    }
+    fn part_two_impl() -> Result<()> {
+        let answer = super::part_two(&INPUT)?;
+        assert_eq!(answer, Answer::Num(164542125272765));
+        Ok(())
+    }
+    #[test]
+    fn disassemble() -> Result<()> {
+        let input = parse_input(&INPUT)?;
+        let listing = Computer::disassemble(&input.program)?;
+        assert_eq!(
+            listing,
+            ["bst A", "bxl 1", "cdv B", "bxl 5", "adv 3", "bxc", "out B", "jnz 0"]
+        );
+        Ok(())
+    }
+    #[test]
+    fn trace() -> Result<()> {
+        let input = parse_input(&TEST)?;
+        let steps = Computer::from(&input).trace(&input.program)?;
+        assert_eq!(steps[0].to_string(), "  0: adv 1  A=364 B=0 C=0");
+        assert_eq!(steps[1].to_string(), "  2: out A  A=364 B=0 C=0 -> 4");
+        let outputs: Vec<u8> = steps.iter().filter_map(|s| s.output).collect();
+        assert_eq!(outputs, [4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+        Ok(())
+    }
+    #[bench]
+    fn part_two(b: &mut Bencher) {
+        part_two_impl().expect("Error");
+        b.iter(|| part_two_impl())
+    }
+}
@@ -0,0 +1,179 @@
+#![feature(test)]
+extern crate test;
+
+pub mod netlist;
+
+use anyhow::{anyhow, Result};
+use common::Answer;
+use netlist::Netlist;
+use std::collections::HashMap;
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    let input = parse_input(input)?;
+    solve_one(&input)
+}
+
+pub fn part_two(input: &str) -> Result<Answer> {
+    let input = parse_input(input)?;
+    solve_two(&input)
+}
+
+/// Day 24 of Advent of Code 2024
+pub struct Day24;
+
+impl common::Day for Day24 {
+    const DAY: u8 = 24;
+    type Input = Input;
+    fn parse(input: &str) -> Result<Input> {
+        parse_input(input)
+    }
+    fn part_one(input: &Input) -> Result<Answer> {
+        solve_one(input)
+    }
+    fn part_two(input: &Input) -> Result<Answer> {
+        solve_two(input)
+    }
+}
+
+pub struct Input {
+    inputs: HashMap<String, bool>,
+    netlist: Netlist,
+}
+
+fn parse_input(input: &str) -> Result<Input> {
+    let (inputs_str, gates_str) = input
+        .trim()
+        .split_once("\n\n")
+        .ok_or(anyhow!("there should be two section"))?;
+
+    let mut inputs = HashMap::new();
+    for line in inputs_str.lines() {
+        let (name, value) = line
+            .split_once(": ")
+            .ok_or(anyhow!("invalid line {}", line))?;
+        let value = parse_bool(value)?;
+        inputs.insert(String::from(name), value);
+    }
+    let netlist = Netlist::parse(gates_str)?;
+    Ok(Input { inputs, netlist })
+}
+
+fn parse_bool(bool_str: &str) -> Result<bool> {
+    if bool_str == "0" {
+        Ok(false)
+    } else if bool_str == "1" {
+        Ok(true)
+    } else {
+        Err(anyhow!("Only 0 or 1 are parsed as bool (not {})", bool_str))
+    }
+}
+
+impl Input {
+    pub fn netlist(&self) -> &Netlist {
+        &self.netlist
+    }
+}
+
+fn solve_one(input: &Input) -> Result<Answer> {
+    let Input { inputs, netlist } = input;
+    let mut wire_inputs = HashMap::new();
+    for (name, &value) in inputs {
+        let wire = netlist
+            .find(name)
+            .ok_or(anyhow!("input {} is not connected", name))?;
+        wire_inputs.insert(wire, value);
+    }
+    let values = netlist.evaluate(&wire_inputs)?;
+    let mut z = 0;
+    for (bit, wire) in netlist.bus('z').into_iter().enumerate() {
+        let value = values[wire].ok_or(anyhow!("all z gates must be initialized"))?;
+        z |= (value as i128) << bit;
+    }
+    Ok(Answer::Num(z))
+}
+
+/// names of the wires whose swap repairs the adder, sorted
+pub fn swapped_wires(input: &Input) -> Result<Vec<String>> {
+    let netlist = &input.netlist;
+    let swaps = netlist
+        .repair(4)?
+        .ok_or(anyhow!("no set of at most 4 swaps repairs the adder"))?;
+    let mut wires: Vec<String> = swaps
+        .into_iter()
+        .flat_map(|(a, b)| [a, b])
+        .map(|w| String::from(netlist.name(w)))
+        .collect();
+    wires.sort();
+    Ok(wires)
+}
+
+fn solve_two(input: &Input) -> Result<Answer> {
+    Ok(Answer::Str(swapped_wires(input)?.join(",")))
+}
+
+// Quickly obtain answers by running
+// cargo test one [-r]
+// cargo test two [-r]
+#[cfg(test)]
+mod day24_tests {
+    use super::*;
+    use common::test_utils::*;
+    use std::sync::LazyLock;
+    use test::Bencher;
+
+    static INPUT: LazyLock<String> = local_file!("input");
+
+    examples!();
+
+    fn part_one_impl() -> Result<()> {
+        let answer = super::part_one(&INPUT)?;
+        assert_eq!(answer, Answer::Num(49430469426918));
+        Ok(())
+    }
+    #[bench]
+    fn part_one(b: &mut Bencher) {
+        part_one_impl().expect("Error");
+        b.iter(|| part_one_impl())
+    }
+    fn part_two_impl() -> Result<()> {
+        let answer = super::part_two(&INPUT)?;
+        assert_eq!(answer, Answer::from("fbq,pbv,qff,qnw,qqp,z16,z23,z36"));
+        Ok(())
+    }
+    #[test]
+    fn adder_check() -> Result<()> {
+        let mut input = parse_input(&INPUT)?;
+        let violations = input.netlist.check_adder();
+        let mut wires: Vec<&str> = violations.iter().map(|v| v.wire.as_str()).collect();
+        wires.sort();
+        wires.dedup();
+        assert_eq!(
+            wires,
+            ["fbq", "pbv", "qff", "qnw", "qqp", "z16", "z23", "z36"]
+        );
+        for (a, b) in input.netlist.repair(4)?.unwrap() {
+            input.netlist.swap_outputs(a, b);
+        }
+        assert!(input.netlist.check_adder().is_empty());
+        assert_eq!(input.netlist.add(1 << 44, (1 << 44) + 3)?, (1 << 45) + 3);
+        Ok(())
+    }
+    #[test]
+    fn inputs_up_to_64_bits() -> Result<()> {
+        // z = x ^ y, wrong but every bit can be evaluated
+        let netlist = |bits: usize| {
+            let gates: Vec<String> = (0..bits)
+                .map(|bit| format!("x{:02} XOR y{:02} -> z{:02}", bit, bit, bit))
+                .collect();
+            Netlist::parse(&gates.join("\n"))
+        };
+        assert!(netlist(64)?.first_wrong_bit(8).is_ok());
+        assert!(netlist(65)?.first_wrong_bit(8).is_err());
+        Ok(())
+    }
+    #[bench]
+    fn part_two(b: &mut Bencher) {
+        part_two_impl().expect("Error");
+        b.iter(|| part_two_impl())
+    }
+}
@@ -0,0 +1,395 @@
+use anyhow::{anyhow, bail, Result};
+use common::regx;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    fmt::Write,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GateType {
+    And,
+    Or,
+    Xor,
+}
+
+impl TryFrom<&str> for GateType {
+    type Error = anyhow::Error;
+    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+        Ok(match value {
+            "AND" => Self::And,
+            "OR" => Self::Or,
+            "XOR" => Self::Xor,
+            _ => Err(anyhow!("not a valid gate_type: {}", value))?,
+        })
+    }
+}
+impl fmt::Display for GateType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                GateType::And => "AND",
+                GateType::Or => "OR",
+                GateType::Xor => "XOR",
+            }
+        )
+    }
+}
+impl GateType {
+    fn apply(&self, lhs: bool, rhs: bool) -> bool {
+        match self {
+            GateType::And => lhs && rhs,
+            GateType::Or => lhs || rhs,
+            GateType::Xor => lhs ^ rhs,
+        }
+    }
+}
+
+/// index of a wire inside its `Netlist`
+pub type Wire = usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gate {
+    pub gate_type: GateType,
+    pub inputs: (Wire, Wire),
+    pub output: Wire,
+}
+
+/// A wire that does not fit into the expected ripple carry adder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub wire: String,
+    pub reason: &'static str,
+}
+
+#[derive(Debug, Clone)]
+pub struct Netlist {
+    names: Vec<String>,
+    index: HashMap<String, Wire>,
+    gates: Vec<Gate>,
+    /// gate driving each wire, None for primary inputs
+    driver: Vec<Option<usize>>,
+}
+
+impl Netlist {
+    /// parses lines of the form `x00 AND y00 -> z00`
+    pub fn parse(gates_str: &str) -> Result<Netlist> {
+        let mut netlist = Netlist {
+            names: Vec::new(),
+            index: HashMap::new(),
+            gates: Vec::new(),
+            driver: Vec::new(),
+        };
+        let gate_regex = regx!(r"^(\w+) (AND|OR|XOR) (\w+) -> (\w+)$");
+        for line in gates_str.lines() {
+            let caps = gate_regex
+                .captures(line)
+                .ok_or(anyhow!("not a valid gate_str {}", line))?;
+            let gate = Gate {
+                gate_type: GateType::try_from(&caps[2])?,
+                inputs: (netlist.wire(&caps[1]), netlist.wire(&caps[3])),
+                output: netlist.wire(&caps[4]),
+            };
+            if netlist.driver[gate.output].is_some() {
+                Err(anyhow!("wire {} is driven twice", &caps[4]))?;
+            }
+            netlist.driver[gate.output] = Some(netlist.gates.len());
+            netlist.gates.push(gate);
+        }
+        Ok(netlist)
+    }
+    fn wire(&mut self, name: &str) -> Wire {
+        if let Some(&wire) = self.index.get(name) {
+            return wire;
+        }
+        self.names.push(String::from(name));
+        self.driver.push(None);
+        self.index.insert(String::from(name), self.names.len() - 1);
+        self.names.len() - 1
+    }
+    pub fn name(&self, wire: Wire) -> &str {
+        &self.names[wire]
+    }
+    pub fn find(&self, name: &str) -> Option<Wire> {
+        self.index.get(name).copied()
+    }
+    /// wires starting with `prefix` followed by a number, ordered by that number
+    pub fn bus(&self, prefix: char) -> Vec<Wire> {
+        let mut bus: Vec<(u32, Wire)> = self
+            .names
+            .iter()
+            .enumerate()
+            .filter_map(|(wire, name)| {
+                let bit = name.strip_prefix(prefix)?.parse().ok()?;
+                Some((bit, wire))
+            })
+            .collect();
+        bus.sort();
+        bus.into_iter().map(|(_, wire)| wire).collect()
+    }
+    fn consumers(&self) -> Vec<Vec<usize>> {
+        let mut consumers = vec![Vec::new(); self.names.len()];
+        for (g, gate) in self.gates.iter().enumerate() {
+            consumers[gate.inputs.0].push(g);
+            consumers[gate.inputs.1].push(g);
+        }
+        consumers
+    }
+
+    /// gates in evaluation order, fails if the netlist contains a loop
+    pub fn topological_order(&self) -> Result<Vec<usize>> {
+        let consumers = self.consumers();
+        let mut missing: Vec<usize> = self
+            .gates
+            .iter()
+            .map(|g| {
+                [g.inputs.0, g.inputs.1]
+                    .iter()
+                    .filter(|&&w| self.driver[w].is_some())
+                    .count()
+            })
+            .collect();
+        let mut ready: VecDeque<usize> =
+            (0..self.gates.len()).filter(|&g| missing[g] == 0).collect();
+        let mut order = Vec::new();
+        while let Some(g) = ready.pop_front() {
+            order.push(g);
+            for &c in consumers[self.gates[g].output].iter() {
+                missing[c] -= 1;
+                if missing[c] == 0 {
+                    ready.push_back(c);
+                }
+            }
+        }
+        if order.len() != self.gates.len() {
+            Err(anyhow!("netlist contains a loop"))?;
+        }
+        Ok(order)
+    }
+
+    /// values of all wires given the values of the primary inputs
+    pub fn evaluate(&self, inputs: &HashMap<Wire, bool>) -> Result<Vec<Option<bool>>> {
+        let mut values = vec![None; self.names.len()];
+        for (&wire, &value) in inputs {
+            values[wire] = Some(value);
+        }
+        for g in self.topological_order()? {
+            let gate = &self.gates[g];
+            if let (Some(lhs), Some(rhs)) = (values[gate.inputs.0], values[gate.inputs.1]) {
+                values[gate.output] = Some(gate.gate_type.apply(lhs, rhs));
+            }
+        }
+        Ok(values)
+    }
+
+    /// z = x + y evaluated by the circuit, z has one bit more than x and y
+    pub fn add(&self, x: u64, y: u64) -> Result<u128> {
+        let mut inputs = HashMap::new();
+        for (bit, &wire) in self.bus('x').iter().enumerate() {
+            inputs.insert(wire, x >> bit & 1 == 1);
+        }
+        for (bit, &wire) in self.bus('y').iter().enumerate() {
+            inputs.insert(wire, y >> bit & 1 == 1);
+        }
+        let values = self.evaluate(&inputs)?;
+        let mut z = 0;
+        for (bit, &wire) in self.bus('z').iter().enumerate() {
+            let value = values[wire].ok_or(anyhow!("{} is not driven", self.name(wire)))?;
+            z |= (value as u128) << bit;
+        }
+        Ok(z)
+    }
+
+    pub fn swap_outputs(&mut self, a: Wire, b: Wire) {
+        let (ga, gb) = (self.driver[a], self.driver[b]);
+        if let Some(g) = ga {
+            self.gates[g].output = b;
+        }
+        if let Some(g) = gb {
+            self.gates[g].output = a;
+        }
+        self.driver.swap(a, b);
+    }
+
+    /// Checks every gate against the ripple carry full adder
+    ///   xN XOR yN -> XN, xN AND yN -> AN, XN XOR cN -> zN, XN AND cN -> BN, AN OR BN -> cN+1
+    /// where bit 0 is a half adder and the last z is the final carry.
+    pub fn check_adder(&self) -> Vec<Violation> {
+        let consumers = self.consumers();
+        let z_bus = self.bus('z');
+        let last_z = z_bus.last().copied();
+        let first = |w: Wire| {
+            let name = self.name(w);
+            name == "x00" || name == "y00"
+        };
+        let is_input = |w: Wire| self.name(w).starts_with(['x', 'y']);
+        let feeds = |w: Wire, gate_type: GateType| {
+            consumers[w]
+                .iter()
+                .any(|&c| self.gates[c].gate_type == gate_type)
+        };
+        let mut violations = Vec::new();
+        let mut report = |wire: Wire, reason| {
+            violations.push(Violation {
+                wire: self.name(wire).to_owned(),
+                reason,
+            })
+        };
+        for gate in self.gates.iter() {
+            let out = gate.output;
+            let from_inputs = is_input(gate.inputs.0) && is_input(gate.inputs.1);
+            let is_z = self.name(out).starts_with('z');
+            let half_adder = first(gate.inputs.0) && first(gate.inputs.1);
+            match gate.gate_type {
+                GateType::Xor if from_inputs && !half_adder => {
+                    if !feeds(out, GateType::Xor) || !feeds(out, GateType::And) {
+                        report(out, "xN XOR yN must feed the sum XOR and the carry AND");
+                    }
+                }
+                GateType::Xor if !from_inputs && !is_z => {
+                    report(out, "XN XOR cN must drive a z output");
+                }
+                GateType::And if !half_adder && !feeds(out, GateType::Or) => {
+                    report(out, "AND must feed the carry OR");
+                }
+                GateType::Or
+                    if Some(out) != last_z
+                        && (!feeds(out, GateType::Xor) || !feeds(out, GateType::And)) =>
+                {
+                    report(out, "carry OR must feed the next XOR and AND");
+                }
+                _ => {}
+            }
+            if is_z && Some(out) != last_z && gate.gate_type != GateType::Xor {
+                report(out, "z outputs except the last must come from a XOR");
+            }
+            if Some(out) == last_z && gate.gate_type != GateType::Or && self.gates.len() > 2 {
+                report(out, "the last z output is the final carry OR");
+            }
+        }
+        violations
+    }
+
+    /// lowest z bit that is wrong for any of the test additions
+    pub fn first_wrong_bit(&self, trials: usize) -> Result<Option<u32>> {
+        let bits = self.bus('x').len() as u32;
+        if bits > 64 {
+            bail!("{} bit inputs do not fit in u64", bits);
+        }
+        let mask = u64::MAX.checked_shr(64 - bits).unwrap_or(0);
+        let mut tests = Vec::new();
+        for bit in 0..bits {
+            tests.push((1 << bit, 0));
+            tests.push((0, 1 << bit));
+            tests.push((1 << bit, 1 << bit));
+        }
+        let mut rng = XorShift(0x2024_1224);
+        for _ in 0..trials {
+            tests.push((rng.next() & mask, rng.next() & mask));
+        }
+        Ok(tests
+            .into_iter()
+            .filter_map(|(x, y)| match (self.add(x, y), x as u128 + y as u128) {
+                (Ok(z), sum) if z == sum => None,
+                (Ok(z), sum) => Some((z ^ sum).trailing_zeros()),
+                (Err(_), _) => Some(0),
+            })
+            .min())
+    }
+
+    /// Smallest set of output swaps (at most max_swaps) after which the circuit
+    /// adds correctly. Only wires reported by `check_adder` are considered.
+    pub fn repair(&self, max_swaps: usize) -> Result<Option<Vec<(Wire, Wire)>>> {
+        let candidates: Vec<Wire> = self
+            .check_adder()
+            .iter()
+            .filter_map(|v| self.find(&v.wire))
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        for depth in 0..=max_swaps {
+            if let Some(found) = self.repair_dfs(&candidates, depth, &mut Vec::new())? {
+                return Ok(Some(found));
+            }
+        }
+        Ok(None)
+    }
+    fn repair_dfs(
+        &self,
+        candidates: &[Wire],
+        depth: usize,
+        swaps: &mut Vec<(Wire, Wire)>,
+    ) -> Result<Option<Vec<(Wire, Wire)>>> {
+        let Some(wrong) = self.first_wrong_bit(64)? else {
+            return Ok(Some(swaps.clone()));
+        };
+        if depth == 0 {
+            return Ok(None);
+        }
+        let used: HashSet<Wire> = swaps.iter().flat_map(|&(a, b)| [a, b]).collect();
+        for (i, &a) in candidates.iter().enumerate() {
+            for &b in candidates[i + 1..].iter() {
+                if used.contains(&a) || used.contains(&b) {
+                    continue;
+                }
+                let mut swapped = self.clone();
+                swapped.swap_outputs(a, b);
+                // every swap has to fix the lowest broken bit, otherwise it is not needed
+                if swapped.first_wrong_bit(64)?.is_some_and(|w| w <= wrong) {
+                    continue;
+                }
+                swaps.push((a, b));
+                if let Some(found) = swapped.repair_dfs(candidates, depth - 1, swaps)? {
+                    return Ok(Some(found));
+                }
+                swaps.pop();
+            }
+        }
+        Ok(None)
+    }
+
+    /// Graphviz representation, wires in `highlight` are drawn red
+    pub fn to_dot(&self, highlight: &[String]) -> String {
+        let mut dot = String::from("digraph circuit {\n  rankdir=LR;\n");
+        for (wire, name) in self.names.iter().enumerate() {
+            let color = if highlight.contains(name) {
+                "red"
+            } else {
+                "black"
+            };
+            let shape = if self.driver[wire].is_none() {
+                "box"
+            } else {
+                "ellipse"
+            };
+            writeln!(dot, "  {} [shape={}, color={}];", name, shape, color).unwrap();
+        }
+        for (g, gate) in self.gates.iter().enumerate() {
+            writeln!(
+                dot,
+                "  g{} [label=\"{}\", shape=diamond];",
+                g, gate.gate_type
+            )
+            .unwrap();
+            writeln!(dot, "  {} -> g{};", self.name(gate.inputs.0), g).unwrap();
+            writeln!(dot, "  {} -> g{};", self.name(gate.inputs.1), g).unwrap();
+            writeln!(dot, "  g{} -> {};", g, self.name(gate.output)).unwrap();
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// small deterministic generator for the test additions
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
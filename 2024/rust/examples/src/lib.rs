@@ -0,0 +1,116 @@
+use proc_macro::TokenStream;
+use std::{collections::HashSet, env, fs, path::Path};
+
+const KEYS: [&str; 5] = ["file", "name", "params", "part1", "part2"];
+
+/// the parts of the day, for days without parameters
+const DEFAULT_SOLVER: &str =
+    "|input: &str, part: u8, _: &::common::test_utils::Params| match part {
+    1 => super::part_one(input),
+    _ => super::part_two(input),
+}";
+
+/// Turns the `examples.toml` next to the `Cargo.toml` of a day into one test per example and
+/// part, e.g.
+///
+/// ```toml
+/// [[example]]
+/// file = "test2"              # variant in the input store
+/// name = "small"              # optional, defaults to the file
+/// params = { size = [7, 7] }  # optional, see `common::test_utils::Params`
+/// part1 = 22
+/// part2 = "6,1"
+/// ```
+///
+/// becomes the tests `example_small_part1` and `example_small_part2`. Without an argument the
+/// tests call `super::part_one(input)` and `super::part_two(input)`, so it belongs in the test
+/// module of the day. Days with parameters pass a closure `|input, part, params| -> Result<_>`.
+#[proc_macro]
+pub fn examples(solver: TokenStream) -> TokenStream {
+    let code = expand(&solver.to_string())
+        .unwrap_or_else(|err| format!("compile_error!({:?});", format!("examples.toml: {}", err)));
+    code.parse().unwrap()
+}
+
+fn expand(solver: &str) -> Result<String, String> {
+    let dir = env::var("CARGO_MANIFEST_DIR").map_err(|err| err.to_string())?;
+    let path = Path::new(&dir).join("examples.toml");
+    let text = fs::read_to_string(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let table: toml::Table = text.parse().map_err(|err| format!("{}", err))?;
+    let examples = match table.get("example") {
+        Some(toml::Value::Array(examples)) => examples.as_slice(),
+        Some(_) => return Err("`example` must be an array of tables, [[example]]".to_owned()),
+        None => &[],
+    };
+    let solver = match solver.trim() {
+        "" => DEFAULT_SOLVER,
+        solver => solver,
+    };
+
+    // the tests have to be rebuilt whenever the examples change
+    let mut code = format!(
+        "const _: &str = include_str!({:?});\n",
+        path.display().to_string()
+    );
+    let mut names = HashSet::new();
+    for (i, example) in examples.iter().enumerate() {
+        let example = example
+            .as_table()
+            .ok_or_else(|| format!("example {} is not a table", i + 1))?;
+        if let Some(key) = example.keys().find(|k| !KEYS.contains(&k.as_str())) {
+            return Err(format!("example {}: unknown key {}", i + 1, key));
+        }
+        let Some(file) = example.get("file").and_then(|f| f.as_str()) else {
+            return Err(format!("example {} has no file", i + 1));
+        };
+        let name = match example.get("name") {
+            Some(name) => name
+                .as_str()
+                .ok_or_else(|| format!("example {}: name must be a string", i + 1))?,
+            None => file,
+        };
+        let name: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let params = match example.get("params") {
+            Some(params @ toml::Value::Table(_)) => params.clone(),
+            Some(_) => return Err(format!("example {}: params must be a table", i + 1)),
+            None => toml::Value::Table(toml::Table::new()),
+        };
+        let params = serde_json::to_string(&params).map_err(|err| err.to_string())?;
+
+        let mut parts = 0;
+        for part in 1..=2 {
+            let expected = match example.get(&format!("part{}", part)) {
+                Some(toml::Value::Integer(answer)) => answer.to_string(),
+                Some(toml::Value::String(answer)) => answer.clone(),
+                Some(_) => {
+                    return Err(format!(
+                        "example {}: part{} must be a number or a string",
+                        i + 1,
+                        part
+                    ))
+                }
+                None => continue,
+            };
+            parts += 1;
+            let test = format!("example_{}_part{}", name, part);
+            if !names.insert(test.clone()) {
+                return Err(format!("two examples are named {}, give one a name", name));
+            }
+            code += &format!(
+                "#[test]
+                fn {test}() {{
+                    ::common::test_utils::check_example(
+                        env!(\"CARGO_PKG_NAME\"), {file:?}, {part}, {params:?}, {expected:?}, {solver},
+                    );
+                }}\n",
+            );
+        }
+        if parts == 0 {
+            return Err(format!("example {} has neither part1 nor part2", i + 1));
+        }
+    }
+    Ok(code)
+}
@@ -1,4 +1,8 @@
+mod day;
+
+pub use day::{project_dir, read_input, run, solve_and_report, Day, YEAR};
 pub use regex;
+pub use runner::{debug, guard, inputs, log, report, trace};
 use std::fmt::Display;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -28,17 +32,15 @@ macro_rules! regx {
 
 #[cfg(feature = "test-utils")]
 pub mod test_utils {
-    use std::fs;
-    pub fn read_from_file(filename: &str) -> String {
-        println!("reading {}", filename);
-        fs::read_to_string(filename)
-            .unwrap_or_else(|msg| panic!("error reading {}: {}", filename, msg))
+    /// `day` is the crate name of the day, e.g. `day03`
+    pub fn read_from_store(day: &str, variant: &str) -> String {
+        crate::inputs::read_for_test(crate::YEAR, day, variant)
     }
 
     #[macro_export]
     macro_rules! local_file {
-        ($file:literal) => {
-            LazyLock::new(|| common::test_utils::read_from_file(&format!("{}", $file)))
+        ($variant:literal) => {
+            LazyLock::new(|| common::test_utils::read_from_store(env!("CARGO_PKG_NAME"), $variant))
         };
     }
     // re-export macro such that test_utils::local_file path can be used.
@@ -0,0 +1,100 @@
+#![feature(test)]
+extern crate test;
+
+use anyhow::Result;
+use common::Answer;
+
+pub fn part_one(input: &str) -> Result<Answer> {
+    let input = parse_input(input)?;
+    solve_one(&input)
+}
+
+pub fn part_two(input: &str) -> Result<Answer> {
+    let input = parse_input(input)?;
+    solve_two(&input)
+}
+
+/// Day 04 of Advent of Code 2025
+pub struct Day04;
+
+impl common::Day for Day04 {
+    const DAY: u8 = 4;
+    type Input = Input;
+    fn parse(input: &str) -> Result<Input> {
+        parse_input(input)
+    }
+    fn part_one(input: &Input) -> Result<Answer> {
+        solve_one(input)
+    }
+    fn part_two(input: &Input) -> Result<Answer> {
+        solve_two(input)
+    }
+}
+
+pub struct Input {
+    nums: Vec<i128>,
+}
+
+fn parse_input(input: &str) -> Result<Input> {
+    // example to collect Vec<Result<T, E>> to Result<Vec<T>, E>
+    let nums: Vec<i128> = input
+        .lines()
+        .map(|l| i128::from_str_radix(l, 10))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Input { nums })
+}
+
+fn solve_one(input: &Input) -> Result<Answer> {
+    let Input { nums } = input;
+    Ok(Answer::Num(nums.iter().sum()))
+}
+
+fn solve_two(input: &Input) -> Result<Answer> {
+    let _unused = input;
+    Ok(Answer::Num(0))
+}
+
+// Quickly obtain answers by running
+// cargo test one [-r]
+// cargo test two [-r]
+#[cfg(test)]
+mod day04_tests {
+    use super::*;
+    use common::test_utils::*;
+    use std::sync::LazyLock;
+    use test::Bencher;
+
+    static TEST: LazyLock<String> = local_file!("test");
+    static INPUT: LazyLock<String> = local_file!("input");
+
+    #[test]
+    fn test_one() -> Result<()> {
+        let answer = super::part_one(&TEST)?;
+        assert_eq!(answer, Answer::Num(0));
+        Ok(())
+    }
+    fn part_one_impl() -> Result<()> {
+        let answer = super::part_one(&INPUT)?;
+        assert_eq!(answer, Answer::Num(0));
+        Ok(())
+    }
+    #[bench]
+    fn part_one(b: &mut Bencher) {
+        b.iter(|| part_one_impl())
+    }
+    #[test]
+    fn test_two() -> Result<()> {
+        let answer = super::part_two(&TEST)?;
+        assert_eq!(answer, Answer::Num(0));
+        Ok(())
+    }
+    fn part_two_impl() -> Result<()> {
+        let answer = super::part_two(&INPUT)?;
+        assert_eq!(answer, Answer::Num(0));
+        Ok(())
+    }
+    #[bench]
+    fn part_two(b: &mut Bencher) {
+        b.iter(|| part_two_impl())
+    }
+}
@@ -0,0 +1,382 @@
+use crate::{
+    history::History,
+    state::{now, State, Submission},
+};
+use anyhow::{bail, Context, Result};
+use std::{fmt, fs, path::Path, str::FromStr, thread, time::Duration};
+
+const USER_AGENT: &str = "aoc repository tooling (tools/aoc)";
+
+/// What the server thought of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Right,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// answered again before the previous wait was over, the answer was not checked
+    TooRecent,
+    /// the part is already solved, the answer was not checked
+    AlreadySolved,
+    Unknown,
+}
+
+impl Verdict {
+    pub fn is_wrong(self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Verdict::Right => "right",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too-high",
+            Verdict::TooLow => "too-low",
+            Verdict::TooRecent => "too-recent",
+            Verdict::AlreadySolved => "already-solved",
+            Verdict::Unknown => "unknown",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "right" => Verdict::Right,
+            "wrong" => Verdict::Wrong,
+            "too-high" => Verdict::TooHigh,
+            "too-low" => Verdict::TooLow,
+            "too-recent" => Verdict::TooRecent,
+            "already-solved" => Verdict::AlreadySolved,
+            "unknown" => Verdict::Unknown,
+            _ => bail!("unknown verdict {}", s),
+        })
+    }
+}
+
+/// The parsed answer page, `message` is the text of its `<article>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    pub verdict: Verdict,
+    pub wait: Option<Duration>,
+    pub message: String,
+}
+
+/// text of the first `<article>` (or the whole page) without tags and extra whitespace
+fn article_text(html: &str) -> String {
+    let html = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// "You have 4m 32s left to wait" or "please wait 5 minutes before trying again"
+fn parse_wait(text: &str) -> Option<Duration> {
+    if let Some(start) = text.find("You have ") {
+        let rest = &text[start + "You have ".len()..];
+        let end = rest.find(" left to wait")?;
+        let mut secs = 0;
+        for token in rest[..end].split_whitespace() {
+            let (num, unit) = token.split_at(token.len() - 1);
+            let num: u64 = num.parse().ok()?;
+            secs += match unit {
+                "h" => num * 3600,
+                "m" => num * 60,
+                "s" => num,
+                _ => return None,
+            };
+        }
+        return Some(Duration::from_secs(secs));
+    }
+    let start = text.to_lowercase().find("please wait ")?;
+    let amount = text[start + "please wait ".len()..]
+        .split_whitespace()
+        .next()?;
+    let minutes = match amount {
+        "one" => 1,
+        "a" => 1,
+        n => n.parse().ok()?,
+    };
+    Some(Duration::from_secs(minutes * 60))
+}
+
+impl Response {
+    pub fn parse(html: &str) -> Response {
+        let message = article_text(html);
+        let verdict = if message.contains("That's the right answer") {
+            Verdict::Right
+        } else if message.contains("That's not the right answer") {
+            if message.contains("answer is too high") {
+                Verdict::TooHigh
+            } else if message.contains("answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            }
+        } else if message.contains("You gave an answer too recently") {
+            Verdict::TooRecent
+        } else if message.contains("Did you already complete it") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown
+        };
+        Response {
+            verdict,
+            wait: parse_wait(&message),
+            message,
+        }
+    }
+}
+
+/// `~/.adventofcode.session`, the same file aoc-cli uses
+pub fn default_session_file() -> Option<std::path::PathBuf> {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+    Some(Path::new(&home).join(".adventofcode.session"))
+}
+
+pub fn read_session(path: &Path) -> Result<String> {
+    let session = fs::read_to_string(path)
+        .with_context(|| format!("reading the session token from {}", path.display()))?;
+    let session = session.trim();
+    if session.is_empty() {
+        bail!("{} is empty", path.display());
+    }
+    Ok(session.trim_start_matches("session=").to_owned())
+}
+
+/// Talks to adventofcode.com, or whatever `base_url` points to. Requests are spaced at
+/// least `min_interval` apart across runs.
+pub struct Client {
+    base_url: String,
+    session: String,
+    min_interval: Duration,
+    state: State,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    pub fn new(base_url: &str, session: String, min_interval: Duration, state: State) -> Client {
+        Client {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session,
+            min_interval,
+            state,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    fn throttle(&self) -> Result<()> {
+        let delay = self.state.throttle_delay(self.min_interval);
+        if !delay.is_zero() {
+            eprintln!("waiting {:?} before the next request", delay);
+            thread::sleep(delay);
+        }
+        self.state.touch_request()
+    }
+    fn send(&self, request: ureq::Request, form: Option<&[(&str, &str)]>) -> Result<String> {
+        self.throttle()?;
+        let request = request.set("Cookie", &format!("session={}", self.session));
+        let response = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(404, _)) => bail!("not found, is the puzzle unlocked yet?"),
+            Err(ureq::Error::Status(code @ (400 | 500), _)) => {
+                bail!(
+                    "server answered {}, is the session token still valid?",
+                    code
+                )
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn fetch(&self, year: u16, day: u8) -> Result<String> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        self.send(self.agent.get(&url), None)
+            .with_context(|| format!("fetching {}", url))
+    }
+
+    /// checks the local state first and does not send answers that are known to be wrong
+    /// or out of bounds, for already solved parts or while the server still wants us to wait
+    pub fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<Response> {
+        if let Some(until) = self.state.wait_until(year, day) {
+            bail!(
+                "the server asked to wait, {}s left before answering {}/{:02} again",
+                until - now(),
+                year,
+                day
+            );
+        }
+        History::load(&self.state, year, day, part)?.check(answer)?;
+
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let level = part.to_string();
+        let html = self
+            .send(
+                self.agent.post(&url),
+                Some(&[("level", &level), ("answer", answer)]),
+            )
+            .with_context(|| format!("submitting to {}", url))?;
+        let response = Response::parse(&html);
+        if let Some(wait) = response.wait {
+            self.state.set_wait(year, day, wait)?;
+        }
+        if !matches!(response.verdict, Verdict::TooRecent | Verdict::Unknown) {
+            self.state.record(
+                year,
+                day,
+                part,
+                &Submission {
+                    at: now(),
+                    verdict: response.verdict,
+                    answer: answer.to_owned(),
+                },
+            )?;
+        }
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    const WRONG: &str = "<main><article><p>That's not the right answer; your answer is too high. \
+        If you're stuck, make sure you're using the full input data. Please wait one minute \
+        before trying again. [<a href=\"/2023/day/22\">Return to Day 22</a>]</p></article></main>";
+    const RIGHT: &str = "<article><p>That's the right answer! You are <em>one gold star</em> \
+        closer to restoring snow operations.</p></article>";
+    const TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait \
+        after submitting an answer before trying again. You have 4m 32s left to wait.</p></article>";
+
+    #[test]
+    fn responses() {
+        let wrong = Response::parse(WRONG);
+        assert_eq!(wrong.verdict, Verdict::TooHigh);
+        assert_eq!(wrong.wait, Some(Duration::from_secs(60)));
+        assert!(wrong.message.starts_with("That's not the right answer"));
+        assert_eq!(Response::parse(RIGHT).verdict, Verdict::Right);
+        let too_recent = Response::parse(TOO_RECENT);
+        assert_eq!(too_recent.verdict, Verdict::TooRecent);
+        assert_eq!(too_recent.wait, Some(Duration::from_secs(272)));
+        let solved = "<article><p>You don't seem to be solving the right level. \
+            Did you already complete it?</p></article>";
+        assert_eq!(Response::parse(solved).verdict, Verdict::AlreadySolved);
+        assert_eq!(
+            parse_wait("please wait 5 minutes before trying again"),
+            Some(Duration::from_secs(300))
+        );
+    }
+
+    /// serves `/2023/day/22/input` and answers `/2023/day/22/answer` like the real site,
+    /// 12 is the right answer for part 1
+    fn mock_server(requests: Arc<AtomicUsize>) -> String {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        std::thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                requests.fetch_add(1, Ordering::SeqCst);
+                let cookie = request
+                    .headers()
+                    .iter()
+                    .find(|h| h.field.equiv("Cookie"))
+                    .map(|h| h.value.to_string());
+                if cookie.as_deref() != Some("session=token") {
+                    request
+                        .respond(
+                            tiny_http::Response::from_string("Please log in").with_status_code(400),
+                        )
+                        .unwrap();
+                    continue;
+                }
+                let response = match request.url() {
+                    "/2023/day/22/input" => tiny_http::Response::from_string("1,0,1~1,2,1\n"),
+                    "/2023/day/22/answer" => {
+                        let mut body = String::new();
+                        request.as_reader().read_to_string(&mut body).unwrap();
+                        if body == "level=1&answer=12" {
+                            tiny_http::Response::from_string(RIGHT)
+                        } else {
+                            tiny_http::Response::from_string(WRONG)
+                        }
+                    }
+                    _ => tiny_http::Response::from_string("404 Not Found").with_status_code(404),
+                };
+                request.respond(response).unwrap();
+            }
+        });
+        url
+    }
+
+    #[test]
+    fn fetch_and_submit_against_mock_server() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let requests = Arc::new(AtomicUsize::new(0));
+        let url = mock_server(requests.clone());
+        let client = Client::new(
+            &url,
+            "token".to_owned(),
+            Duration::ZERO,
+            State::new(dir.path()),
+        );
+
+        assert_eq!(client.fetch(2023, 22)?, "1,0,1~1,2,1\n");
+        assert!(format!("{:#}", client.fetch(2023, 26).unwrap_err()).contains("unlocked"));
+        let stranger = Client::new(
+            &url,
+            "other".to_owned(),
+            Duration::ZERO,
+            State::new(dir.path()),
+        );
+        assert!(format!("{:#}", stranger.fetch(2023, 22).unwrap_err()).contains("session"));
+
+        let wrong = client.submit(2023, 22, 1, "517")?;
+        assert_eq!(wrong.verdict, Verdict::TooHigh);
+        let sent = requests.load(Ordering::SeqCst);
+        // the server asked for a minute, nothing is sent until then
+        assert!(format!("{:#}", client.submit(2023, 22, 1, "12").unwrap_err()).contains("wait"));
+        fs::remove_file(dir.path().join("2023/22/wait_until"))?;
+        let again = client.submit(2023, 22, 1, "517").unwrap_err();
+        assert!(format!("{:#}", again).contains("already rejected (too-high)"));
+        assert_eq!(requests.load(Ordering::SeqCst), sent);
+
+        let out_of_bounds = client.submit(2023, 22, 1, "600").unwrap_err();
+        assert!(format!("{:#}", out_of_bounds).contains("too high, 517 already was"));
+        assert_eq!(requests.load(Ordering::SeqCst), sent);
+
+        assert_eq!(client.submit(2023, 22, 1, "12")?.verdict, Verdict::Right);
+        assert!(client.submit(2023, 22, 1, "13").is_err());
+        let verdicts: Vec<Verdict> = State::new(dir.path())
+            .submissions(2023, 22, 1)?
+            .iter()
+            .map(|s| s.verdict)
+            .collect();
+        assert_eq!(verdicts, vec![Verdict::TooHigh, Verdict::Right]);
+        Ok(())
+    }
+}
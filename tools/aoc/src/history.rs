@@ -0,0 +1,156 @@
+use crate::{
+    client::Verdict,
+    state::{State, Submission},
+};
+use anyhow::{bail, Result};
+use std::fmt;
+
+/// `2023-12-22 06:14 UTC` for unix seconds, days to civil date as in Howard Hinnant's `civil_from_days`
+fn format_time(secs: u64) -> String {
+    let days = (secs / 86400) as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let doe = days.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    let minutes = secs % 86400 / 60;
+    format!(
+        "{}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        minutes / 60,
+        minutes % 60
+    )
+}
+
+/// All guesses for one part, numeric answers rejected as too low/high bound the right one.
+pub struct History {
+    pub part: u8,
+    pub submissions: Vec<Submission>,
+}
+
+impl History {
+    pub fn load(state: &State, year: u16, day: u8, part: u8) -> Result<History> {
+        Ok(History {
+            part,
+            submissions: state.submissions(year, day, part)?,
+        })
+    }
+    pub fn solved(&self) -> Option<&Submission> {
+        self.submissions
+            .iter()
+            .find(|s| s.verdict == Verdict::Right)
+    }
+    /// exclusive (lower, upper) bound of the right answer
+    pub fn bounds(&self) -> (Option<i128>, Option<i128>) {
+        let numbers = |verdict| {
+            self.submissions
+                .iter()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+        (
+            numbers(Verdict::TooLow).max(),
+            numbers(Verdict::TooHigh).min(),
+        )
+    }
+    /// fails for answers that can't be right given what the server already said
+    pub fn check(&self, answer: &str) -> Result<()> {
+        if let Some(right) = self.solved() {
+            bail!("part {} is already solved with {}", self.part, right.answer);
+        }
+        if let Some(wrong) = self
+            .submissions
+            .iter()
+            .find(|s| s.verdict.is_wrong() && s.answer == answer)
+        {
+            bail!("{} was already rejected ({})", answer, wrong.verdict);
+        }
+        if let Ok(num) = answer.parse::<i128>() {
+            match self.bounds() {
+                (Some(low), _) if num <= low => {
+                    bail!("{} is too low, {} already was", answer, low)
+                }
+                (_, Some(high)) if num >= high => {
+                    bail!("{} is too high, {} already was", answer, high)
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for History {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "part{}: ", self.part)?;
+        match (self.solved(), self.bounds()) {
+            (Some(right), _) => write!(f, "solved with {}", right.answer)?,
+            (None, (None, None)) if self.submissions.is_empty() => write!(f, "nothing submitted")?,
+            (None, (None, None)) => write!(f, "unsolved")?,
+            (None, (low, high)) => {
+                let low = low
+                    .map(|l| l.to_string())
+                    .unwrap_or_else(|| "..".to_owned());
+                let high = high
+                    .map(|h| h.to_string())
+                    .unwrap_or_else(|| "..".to_owned());
+                write!(f, "unsolved, between {} and {} (exclusive)", low, high)?
+            }
+        }
+        for s in self.submissions.iter() {
+            write!(
+                f,
+                "\n  {}  {:<14} {}",
+                format_time(s.at),
+                s.verdict,
+                s.answer
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submission(verdict: Verdict, answer: &str) -> Submission {
+        Submission {
+            at: 1703225640,
+            verdict,
+            answer: answer.to_owned(),
+        }
+    }
+
+    #[test]
+    fn bounds_and_checks() {
+        let mut history = History {
+            part: 1,
+            submissions: vec![
+                submission(Verdict::TooHigh, "517"),
+                submission(Verdict::TooLow, "300"),
+                submission(Verdict::TooHigh, "600"),
+                submission(Verdict::Wrong, "450"),
+            ],
+        };
+        assert_eq!(history.bounds(), (Some(300), Some(517)));
+        assert!(history.check("517").is_err());
+        assert!(history.check("520").is_err());
+        assert!(history.check("300").is_err());
+        assert!(history.check("450").is_err());
+        assert!(history.check("451").is_ok());
+        assert!(history.check("abc").is_ok());
+        assert!(history.to_string().starts_with(
+            "part1: unsolved, between 300 and 517 (exclusive)\n  2023-12-22 06:14 UTC"
+        ));
+
+        history.submissions.push(submission(Verdict::Right, "451"));
+        assert!(history.check("452").is_err());
+        assert!(history.to_string().starts_with("part1: solved with 451"));
+    }
+}
@@ -0,0 +1,256 @@
+use anyhow::{bail, Context, Result};
+use client::{Client, Verdict};
+use history::History;
+use state::{State, Submission};
+use std::{
+    io::{self, IsTerminal, Read},
+    path::PathBuf,
+    time::Duration,
+};
+use store::{Key, Store};
+use structopt::StructOpt;
+use target::Target;
+
+mod client;
+mod history;
+mod scaffold;
+mod state;
+mod store;
+mod target;
+mod watch;
+
+#[derive(Debug, StructOpt)]
+struct Remote {
+    /// where the puzzles are served, point it at a local server for testing
+    #[structopt(long, env = "AOC_BASE_URL", default_value = "https://adventofcode.com")]
+    base_url: String,
+    /// file holding the session cookie, defaults to ~/.adventofcode.session
+    #[structopt(long, env = "AOC_SESSION_FILE", parse(from_os_str))]
+    session_file: Option<PathBuf>,
+    /// minimum seconds between two requests to the server
+    #[structopt(long, default_value = "5")]
+    throttle: u64,
+}
+
+impl Remote {
+    fn client(&self, store: &Store) -> Result<Client> {
+        let Some(session_file) = self
+            .session_file
+            .clone()
+            .or_else(client::default_session_file)
+        else {
+            bail!("no session file, pass --session-file");
+        };
+        Ok(Client::new(
+            &self.base_url,
+            client::read_session(&session_file)?,
+            Duration::from_secs(self.throttle),
+            State::new(store.state_dir()),
+        ))
+    }
+}
+
+fn check_part(part: u8) -> Result<()> {
+    if !(1..=2).contains(&part) {
+        bail!("part must be 1 or 2");
+    }
+    Ok(())
+}
+
+/// the answer of `part` in text runner output (`part1: 42`), or the whole text
+fn answer_from_output(output: &str, part: u8) -> Option<String> {
+    let prefix = format!("part{}: ", part);
+    match output.lines().find_map(|l| l.strip_prefix(&prefix)) {
+        Some(answer) => Some(answer.trim().to_owned()),
+        None => Some(output.trim().to_owned()).filter(|a| !a.is_empty() && !a.contains('\n')),
+    }
+}
+
+#[derive(Debug, StructOpt)]
+#[structopt(about = "repository tooling for the advent of code solutions")]
+enum Opt {
+    /// copies inputs from a directory or .tar/.tar.gz in `<year>/<day>/<variant>` layout into the store
+    Import {
+        #[structopt(parse(from_os_str))]
+        from: PathBuf,
+        /// replace inputs that already exist with different content
+        #[structopt(long)]
+        force: bool,
+    },
+    /// writes the store (or one year of it) to a directory or .tar/.tar.gz
+    Export {
+        #[structopt(parse(from_os_str))]
+        to: PathBuf,
+        #[structopt(long)]
+        year: Option<u16>,
+    },
+    /// lists the stored inputs
+    List { year: Option<u16> },
+    /// downloads the puzzle input into the store
+    Fetch {
+        year: u16,
+        day: u8,
+        /// download again even if the input is already stored
+        #[structopt(long)]
+        force: bool,
+        #[structopt(flatten)]
+        remote: Remote,
+    },
+    /// submits an answer, without `answer` it is taken from runner output on stdin
+    Submit {
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: Option<String>,
+        #[structopt(flatten)]
+        remote: Remote,
+    },
+    /// creates a day from the template and registers it in the year's project
+    New { year: u16, day: u8 },
+    /// shows the submissions and what is known about the right answers
+    History { year: u16, day: u8 },
+    /// re-runs the day's tests and its real input whenever its sources or inputs change
+    Watch { year: u16, day: u8 },
+    /// solves the day, `input` is a file or `-` for stdin, the store's input by default
+    Run {
+        year: u16,
+        day: u8,
+        input: Option<String>,
+        /// more runner options after `--`, e.g. `-- --format json`
+        #[structopt(last = true)]
+        options: Vec<String>,
+    },
+    /// prints a random input of the day for stress tests, e.g.
+    /// `aoc gen 2024 16 --size 1000 --seed 7 | aoc run 2024 16 -`
+    Gen {
+        year: u16,
+        day: u8,
+        /// what the input scales with, depends on the day
+        #[structopt(long)]
+        size: usize,
+        #[structopt(long, default_value = "0")]
+        seed: u64,
+    },
+    /// logs a guess made without `aoc submit`, verdict is right|wrong|too-high|too-low
+    Record {
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: String,
+        verdict: Verdict,
+    },
+}
+
+fn main() -> Result<()> {
+    let store = Store::locate();
+    match Opt::from_args() {
+        Opt::Import { from, force } => {
+            let summary = store.import(&from, force)?;
+            println!("{}", summary);
+        }
+        Opt::Export { to, year } => {
+            let count = store.export(&to, year)?;
+            println!("exported {} files to {}", count, to.display());
+        }
+        Opt::List { year } => {
+            for key in store
+                .entries()?
+                .iter()
+                .filter(|k| year.is_none_or(|y| k.year == y))
+            {
+                println!("{}", key);
+            }
+        }
+        Opt::Fetch {
+            year,
+            day,
+            force,
+            remote,
+        } => {
+            let key = Key {
+                year,
+                day,
+                variant: "input".to_owned(),
+            };
+            if store.path(&key).exists() && !force {
+                println!(
+                    "{} is already stored, use --force to download it again",
+                    key
+                );
+                return Ok(());
+            }
+            let input = remote.client(&store)?.fetch(year, day)?;
+            store.write(&key, input.as_bytes())?;
+            println!("wrote {} ({} bytes)", key, input.len());
+        }
+        Opt::Submit {
+            year,
+            day,
+            part,
+            answer,
+            remote,
+        } => {
+            check_part(part)?;
+            let answer = match answer {
+                Some(answer) => answer,
+                None if io::stdin().is_terminal() => bail!("no answer given and nothing piped"),
+                None => {
+                    let mut output = String::new();
+                    io::stdin().read_to_string(&mut output)?;
+                    answer_from_output(&output, part)
+                        .with_context(|| format!("no part{} answer in the piped output", part))?
+                }
+            };
+            let response = remote.client(&store)?.submit(year, day, part, &answer)?;
+            println!("{}: {}", response.verdict, response.message);
+            if response.verdict != Verdict::Right {
+                std::process::exit(1);
+            }
+        }
+        Opt::New { year, day } => {
+            for path in scaffold::Scaffold::locate().new_day(year, day, &store)? {
+                println!("wrote {}", path.display());
+            }
+        }
+        Opt::History { year, day } => {
+            let state = State::new(store.state_dir());
+            for part in 1..=2 {
+                println!("{}", History::load(&state, year, day, part)?);
+            }
+        }
+        Opt::Watch { year, day } => watch::watch(year, day, &store)?,
+        Opt::Run {
+            year,
+            day,
+            input,
+            options,
+        } => {
+            let mut runner_options = target::input_options(input.as_deref())?;
+            runner_options.extend(options);
+            Target::locate(&scaffold::Scaffold::locate(), year, day)?.run(&runner_options)?;
+        }
+        Opt::Gen {
+            year,
+            day,
+            size,
+            seed,
+        } => Target::locate(&scaffold::Scaffold::locate(), year, day)?
+            .run(&target::generate_options(size, seed))?,
+        Opt::Record {
+            year,
+            day,
+            part,
+            answer,
+            verdict,
+        } => {
+            check_part(part)?;
+            let submission = Submission {
+                at: state::now(),
+                verdict,
+                answer,
+            };
+            State::new(store.state_dir()).record(year, day, part, &submission)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,305 @@
+use crate::store::{Key, Store};
+use anyhow::{bail, Context, Result};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const WORKSPACE_CARGO: &str = include_str!("../templates/workspace/Cargo.toml");
+const WORKSPACE_LIB: &str = include_str!("../templates/workspace/lib.rs");
+const WORKSPACE_MAIN: &str = include_str!("../templates/workspace/main.rs");
+const MODULE: &str = include_str!("../templates/module/mod.rs");
+
+fn render(template: &str, year: u16, day: u8) -> String {
+    template
+        .replace("{{YEAR}}", &year.to_string())
+        .replace("{{DD}}", &format!("{:02}", day))
+        .replace("{{D}}", &day.to_string())
+}
+
+/// `dayNN` crates in a workspace (2024 on) or `src/dayNN` modules registered in
+/// `main.rs` with `solver.add` (2022, 2023)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layout {
+    Workspace,
+    Modules,
+}
+
+/// inserts `line` in front of the first line whose key is bigger, or after the last keyed line
+fn insert_sorted(text: &str, line: &str, key: impl Fn(&str) -> Option<u8>, day: u8) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let keyed: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, l)| key(l).map(|k| (i, k)))
+        .collect();
+    let at = match keyed.iter().find(|(_, k)| *k > day) {
+        Some((i, _)) => *i,
+        None => keyed.last().map(|(i, _)| i + 1).unwrap_or(lines.len()),
+    };
+    let mut out: Vec<&str> = lines[..at].to_vec();
+    out.push(line);
+    out.extend_from_slice(&lines[at..]);
+    let mut out = out.join("\n");
+    if text.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+fn day_of(line: &str, prefix: &str, suffix: &str) -> Option<u8> {
+    line.trim()
+        .strip_prefix(prefix)?
+        .strip_suffix(suffix)?
+        .parse()
+        .ok()
+}
+
+/// adds `"dayNN",` to the workspace `members` list unless it is there already
+fn add_member(cargo_toml: &str, day: u8) -> Result<Option<String>> {
+    let member = format!("\"day{:02}\",", day);
+    if cargo_toml.lines().any(|l| l.trim() == member) {
+        return Ok(None);
+    }
+    let Some(start) = cargo_toml.find("members = [") else {
+        bail!("no members list in the workspace Cargo.toml");
+    };
+    let end = start
+        + cargo_toml[start..]
+            .find(']')
+            .context("unclosed members list")?;
+    let (head, tail) = cargo_toml.split_at(start);
+    let list = &tail[..end - start];
+    let list = if list.lines().any(|l| day_of(l, "\"day", "\",").is_some()) {
+        insert_sorted(
+            list,
+            &format!("    {}", member),
+            |l| day_of(l, "\"day", "\","),
+            day,
+        )
+    } else {
+        format!("{}    {}\n", list, member)
+    };
+    Ok(Some(format!("{}{}{}", head, list, &tail[end - start..])))
+}
+
+/// adds `mod dayNN;` and the two `solver.add` lines to a `main.rs`
+fn add_module(main_rs: &str, day: u8) -> Result<Option<String>> {
+    let module = format!("mod day{:02};", day);
+    let register = format!("solver.add({}, 1, day{:02}::part_one);", day, day);
+    let has_module = main_rs.lines().any(|l| l.trim() == module);
+    let has_register = main_rs.lines().any(|l| l.trim() == register);
+    if has_module && has_register {
+        return Ok(None);
+    }
+    let mut main_rs = main_rs.to_owned();
+    if !has_module {
+        let anchor = |l: &str| match l.trim() {
+            "mod common;" => Some(0),
+            l => day_of(l, "mod day", ";"),
+        };
+        main_rs = insert_sorted(&main_rs, &module, anchor, day);
+    }
+    if !has_register {
+        let Some(indent) = main_rs
+            .lines()
+            .find(|l| l.trim().starts_with("solver.add("))
+            .map(|l| &l[..l.len() - l.trim_start().len()])
+        else {
+            bail!("no solver.add calls in main.rs");
+        };
+        let registered = |l: &str| {
+            let l = l.trim().strip_prefix("solver.add(")?;
+            l[..l.find(',')?].parse().ok()
+        };
+        let lines = format!(
+            "{indent}solver.add({day}, 1, day{day:02}::part_one);\n{indent}solver.add({day}, 2, day{day:02}::part_two);",
+            indent = indent.to_owned(),
+            day = day
+        );
+        main_rs = insert_sorted(&main_rs, &lines, registered, day);
+    }
+    Ok(Some(main_rs))
+}
+
+/// Creates new days from the templates, in the layout of the year's project.
+pub struct Scaffold {
+    repo: PathBuf,
+}
+
+impl Scaffold {
+    pub fn new(repo: impl Into<PathBuf>) -> Scaffold {
+        Scaffold { repo: repo.into() }
+    }
+    /// the repository this tool is part of
+    pub fn locate() -> Scaffold {
+        let repo = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        Scaffold::new(fs::canonicalize(&repo).unwrap_or(repo))
+    }
+    pub fn repo(&self) -> &Path {
+        &self.repo
+    }
+    /// the cargo project of a year and how its days are laid out
+    pub fn project(&self, year: u16) -> Result<(PathBuf, Layout)> {
+        let project = match year {
+            2022 => self.repo.join("2022/rust/aoc"),
+            _ => self.repo.join(year.to_string()).join("rust"),
+        };
+        let cargo_toml = fs::read_to_string(project.join("Cargo.toml"))
+            .with_context(|| format!("no rust project for {} at {}", year, project.display()))?;
+        let layout = match year {
+            _ if cargo_toml.contains("[workspace]") => Layout::Workspace,
+            2022 | 2023 => Layout::Modules,
+            _ => bail!("don't know how days are laid out in {}", project.display()),
+        };
+        Ok((project, layout))
+    }
+
+    /// writes the day from the template, an existing day is only touched if it still is the
+    /// untouched template, returns the files that changed
+    pub fn new_day(&self, year: u16, day: u8, store: &Store) -> Result<Vec<PathBuf>> {
+        if !(1..=25).contains(&day) {
+            bail!("day must be within 1..=25");
+        }
+        let (project, layout) = self.project(year)?;
+        let mut files: Vec<(PathBuf, String)> = Vec::new();
+        match layout {
+            Layout::Workspace => {
+                let crate_dir = project.join(format!("day{:02}", day));
+                files.push((
+                    crate_dir.join("Cargo.toml"),
+                    render(WORKSPACE_CARGO, year, day),
+                ));
+                files.push((
+                    crate_dir.join("src/lib.rs"),
+                    render(WORKSPACE_LIB, year, day),
+                ));
+                files.push((
+                    crate_dir.join("src/main.rs"),
+                    render(WORKSPACE_MAIN, year, day),
+                ));
+            }
+            Layout::Modules => {
+                let module = project.join(format!("src/day{:02}/mod.rs", day));
+                files.push((module, render(MODULE, year, day)));
+            }
+        }
+        for (path, content) in files.iter() {
+            match fs::read_to_string(path) {
+                Ok(existing) if existing != *content => {
+                    bail!(
+                        "{} exists and is not the template, not overwriting it",
+                        path.display()
+                    )
+                }
+                _ => {}
+            }
+        }
+
+        let mut changed = Vec::new();
+        for (path, content) in files {
+            if !path.exists() {
+                fs::create_dir_all(path.parent().unwrap())?;
+                fs::write(&path, content)?;
+                changed.push(path);
+            }
+        }
+        let (registry, updated) = match layout {
+            Layout::Workspace => {
+                let path = project.join("Cargo.toml");
+                let updated = add_member(&fs::read_to_string(&path)?, day)?;
+                (path, updated)
+            }
+            Layout::Modules => {
+                let path = project.join("src/main.rs");
+                let updated = add_module(&fs::read_to_string(&path)?, day)?;
+                (path, updated)
+            }
+        };
+        if let Some(updated) = updated {
+            fs::write(&registry, updated)?;
+            changed.push(registry);
+        }
+        for variant in ["test", "input"] {
+            let key = Key {
+                year,
+                day,
+                variant: variant.to_owned(),
+            };
+            if !store.path(&key).exists() {
+                store.write(&key, b"")?;
+                changed.push(store.path(&key));
+            }
+        }
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn templates_match_the_placeholder_days() -> Result<()> {
+        let repo = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        let lib = fs::read_to_string(repo.join("2025/rust/day05/src/lib.rs"))?;
+        assert_eq!(render(WORKSPACE_LIB, 2025, 5), lib);
+        let cargo = fs::read_to_string(repo.join("2025/rust/day12/Cargo.toml"))?;
+        assert_eq!(render(WORKSPACE_CARGO, 2025, 12), cargo);
+        let module = fs::read_to_string(repo.join("2023/rust/src/day00/mod.rs"))?;
+        assert_eq!(MODULE, module);
+        Ok(())
+    }
+
+    #[test]
+    fn registration() -> Result<()> {
+        let members =
+            "[workspace]\nmembers = [\n    \"common\",\n    \"day01\",\n    \"day03\",\n]\n";
+        let added = add_member(members, 2)?.unwrap();
+        assert_eq!(
+            added,
+            "[workspace]\nmembers = [\n    \"common\",\n    \"day01\",\n    \"day02\",\n    \"day03\",\n]\n"
+        );
+        assert_eq!(add_member(&added, 2)?, None);
+        let empty = add_member("[workspace]\nmembers = [\n    \"common\",\n]\n", 1)?.unwrap();
+        assert!(empty.contains("    \"common\",\n    \"day01\",\n]"));
+
+        let main = "mod common;\nmod day01;\n\nfn main() {\n    solver.add(1, 1, day01::part_one);\n    solver.add(1, 2, day01::part_two);\n    solver.solve();\n}\n";
+        let added = add_module(main, 2)?.unwrap();
+        assert_eq!(
+            added,
+            "mod common;\nmod day01;\nmod day02;\n\nfn main() {\n    solver.add(1, 1, day01::part_one);\n    solver.add(1, 2, day01::part_two);\n    solver.add(2, 1, day02::part_one);\n    solver.add(2, 2, day02::part_two);\n    solver.solve();\n}\n"
+        );
+        assert_eq!(add_module(&added, 2)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn new_day_in_a_workspace() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let project = dir.path().join("2030/rust");
+        fs::create_dir_all(&project)?;
+        fs::write(
+            project.join("Cargo.toml"),
+            "[workspace]\nmembers = [\n    \"common\",\n]\n",
+        )?;
+        let store = Store::new(dir.path().join("inputs"));
+        let scaffold = Scaffold::new(dir.path());
+
+        let changed = scaffold.new_day(2030, 7, &store)?;
+        assert_eq!(changed.len(), 6);
+        let lib = fs::read_to_string(project.join("day07/src/lib.rs"))?;
+        assert!(lib.contains("/// Day 07 of Advent of Code 2030\npub struct Day07;"));
+        assert!(lib.contains("const DAY: u8 = 7;"));
+        assert!(fs::read_to_string(project.join("Cargo.toml"))?.contains("\"day07\","));
+        assert!(store
+            .path(&Key::from_path(Path::new("2030/07/test")).unwrap())
+            .exists());
+
+        // the untouched template can be scaffolded again, a solved day can't
+        assert!(scaffold.new_day(2030, 7, &store)?.is_empty());
+        fs::write(project.join("day07/src/lib.rs"), "// solved")?;
+        assert!(scaffold.new_day(2030, 7, &store).is_err());
+        Ok(())
+    }
+}
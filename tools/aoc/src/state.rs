@@ -0,0 +1,105 @@
+use crate::client::Verdict;
+use anyhow::{Context, Result};
+use std::{
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// One answer sent to the server, stored as `<unix secs>\t<verdict>\t<answer>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    pub at: u64,
+    pub verdict: Verdict,
+    pub answer: String,
+}
+
+/// Everything `aoc fetch`/`aoc submit` remember between runs, kept next to the inputs
+/// in `<store>/.aoc`:
+/// - `last_request` unix secs of the last request to the server
+/// - `<year>/<day>/wait_until` unix secs before which the server refuses answers
+/// - `<year>/<day>/part<N>` the submissions
+pub struct State {
+    dir: PathBuf,
+}
+
+impl State {
+    pub fn new(dir: impl Into<PathBuf>) -> State {
+        State { dir: dir.into() }
+    }
+    fn puzzle_dir(&self, year: u16, day: u8) -> PathBuf {
+        self.dir.join(year.to_string()).join(format!("{:02}", day))
+    }
+    fn read_secs(&self, path: PathBuf) -> Option<u64> {
+        fs::read_to_string(path).ok()?.trim().parse().ok()
+    }
+    fn write(&self, path: PathBuf, content: &str) -> Result<()> {
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, content).with_context(|| format!("writing {}", path.display()))
+    }
+
+    /// how long to sleep so that requests are at least `interval` apart
+    pub fn throttle_delay(&self, interval: Duration) -> Duration {
+        match self.read_secs(self.dir.join("last_request")) {
+            Some(last) => {
+                (Duration::from_secs(last) + interval).saturating_sub(Duration::from_secs(now()))
+            }
+            None => Duration::ZERO,
+        }
+    }
+    pub fn touch_request(&self) -> Result<()> {
+        self.write(self.dir.join("last_request"), &now().to_string())
+    }
+
+    pub fn wait_until(&self, year: u16, day: u8) -> Option<u64> {
+        self.read_secs(self.puzzle_dir(year, day).join("wait_until"))
+            .filter(|&until| until > now())
+    }
+    pub fn set_wait(&self, year: u16, day: u8, wait: Duration) -> Result<()> {
+        let until = now() + wait.as_secs();
+        self.write(
+            self.puzzle_dir(year, day).join("wait_until"),
+            &until.to_string(),
+        )
+    }
+
+    pub fn submissions(&self, year: u16, day: u8, part: u8) -> Result<Vec<Submission>> {
+        let path = self.puzzle_dir(year, day).join(format!("part{}", part));
+        let Ok(content) = fs::read_to_string(&path) else {
+            return Ok(Vec::new());
+        };
+        content
+            .lines()
+            .filter(|l| !l.is_empty())
+            .map(|line| {
+                let mut fields = line.splitn(3, '\t');
+                let (Some(at), Some(verdict), Some(answer)) =
+                    (fields.next(), fields.next(), fields.next())
+                else {
+                    anyhow::bail!("bad line {:?} in {}", line, path.display());
+                };
+                Ok(Submission {
+                    at: at.parse()?,
+                    verdict: verdict.parse()?,
+                    answer: answer.to_owned(),
+                })
+            })
+            .collect()
+    }
+    pub fn record(&self, year: u16, day: u8, part: u8, submission: &Submission) -> Result<()> {
+        let path = self.puzzle_dir(year, day).join(format!("part{}", part));
+        let mut content = fs::read_to_string(&path).unwrap_or_default();
+        content.push_str(&format!(
+            "{}\t{}\t{}\n",
+            submission.at, submission.verdict, submission.answer
+        ));
+        self.write(path, &content)
+    }
+}
@@ -0,0 +1,310 @@
+use anyhow::{bail, Context, Result};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use std::{
+    env, fmt,
+    fs::{self, File},
+    io::{Read, Write},
+    path::{Component, Path, PathBuf},
+};
+
+/// (year, day, variant) of one stored file, variants are `input`, `test`, `test2`, ...
+/// day 0 is the template day some years have
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Key {
+    pub year: u16,
+    pub day: u8,
+    pub variant: String,
+}
+
+impl Key {
+    /// parses the last three components of `<year>/<day>/<variant>`
+    pub fn from_path(path: &Path) -> Option<Key> {
+        let parts: Vec<&str> = path
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(s) => s.to_str(),
+                _ => None,
+            })
+            .collect();
+        let [.., year, day, variant] = parts[..] else {
+            return None;
+        };
+        let year: u16 = year.parse().ok().filter(|y| (2015..=2100).contains(y))?;
+        let day: u8 = day.parse().ok().filter(|d| (0..=25).contains(d))?;
+        let valid = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-';
+        if variant.is_empty() || !variant.chars().all(valid) {
+            return None;
+        }
+        Some(Key {
+            year,
+            day,
+            variant: variant.to_owned(),
+        })
+    }
+    fn relative_path(&self) -> PathBuf {
+        PathBuf::from(self.year.to_string())
+            .join(format!("{:02}", self.day))
+            .join(&self.variant)
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{:02}/{}", self.year, self.day, self.variant)
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub added: usize,
+    pub replaced: usize,
+    pub unchanged: usize,
+    pub conflicts: Vec<Key>,
+    pub skipped: Vec<PathBuf>,
+}
+
+impl fmt::Display for ImportSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "added {}, replaced {}, unchanged {}",
+            self.added, self.replaced, self.unchanged
+        )?;
+        for key in self.conflicts.iter() {
+            write!(f, "\nkept existing {} (differs, use --force)", key)?;
+        }
+        for path in self.skipped.iter() {
+            write!(
+                f,
+                "\nskipped {} (not <year>/<day>/<variant>)",
+                path.display()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn is_tarball(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+    name.ends_with(".tar") || name.ends_with(".tar.gz") || name.ends_with(".tgz")
+}
+
+fn is_gzip(path: &Path) -> bool {
+    let name = path.to_string_lossy();
+    name.ends_with(".gz") || name.ends_with(".tgz")
+}
+
+/// The git-ignored `inputs/<year>/<day>/<variant>` tree the runners and tests read from.
+pub struct Store {
+    root: PathBuf,
+}
+
+impl Store {
+    pub fn new(root: impl Into<PathBuf>) -> Store {
+        Store { root: root.into() }
+    }
+    /// `AOC_INPUTS` or the `inputs` folder at the top of the repository
+    pub fn locate() -> Store {
+        match env::var_os("AOC_INPUTS") {
+            Some(dir) => Store::new(dir),
+            None => Store::new(Path::new(env!("CARGO_MANIFEST_DIR")).join("../../inputs")),
+        }
+    }
+    /// throttling and submissions of `aoc fetch`/`aoc submit`, never exported
+    pub fn state_dir(&self) -> PathBuf {
+        self.root.join(".aoc")
+    }
+    pub fn path(&self, key: &Key) -> PathBuf {
+        self.root.join(key.relative_path())
+    }
+    pub fn write(&self, key: &Key, content: &[u8]) -> Result<()> {
+        let path = self.path(key);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, content).with_context(|| format!("writing {}", path.display()))
+    }
+    pub fn entries(&self) -> Result<Vec<Key>> {
+        let mut keys = Vec::new();
+        if !self.root.exists() {
+            return Ok(keys);
+        }
+        for year in fs::read_dir(&self.root)? {
+            let year = year?.path();
+            // skips files and the `.aoc` state folder
+            if !year.is_dir()
+                || year
+                    .file_name()
+                    .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+            {
+                continue;
+            }
+            for day in fs::read_dir(&year)? {
+                let day = day?.path();
+                if !day.is_dir() {
+                    continue;
+                }
+                for file in fs::read_dir(day)? {
+                    let file = file?.path();
+                    if let Some(key) = file.strip_prefix(&self.root).ok().and_then(Key::from_path) {
+                        keys.push(key);
+                    }
+                }
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+
+    fn import_one(
+        &self,
+        key: Key,
+        content: Vec<u8>,
+        force: bool,
+        summary: &mut ImportSummary,
+    ) -> Result<()> {
+        match fs::read(self.path(&key)) {
+            Ok(existing) if existing == content => summary.unchanged += 1,
+            Ok(_) if !force => summary.conflicts.push(key),
+            Ok(_) => {
+                self.write(&key, &content)?;
+                summary.replaced += 1;
+            }
+            Err(_) => {
+                self.write(&key, &content)?;
+                summary.added += 1;
+            }
+        }
+        Ok(())
+    }
+    /// imports a directory or tarball, works completely offline
+    pub fn import(&self, from: &Path, force: bool) -> Result<ImportSummary> {
+        let mut summary = ImportSummary::default();
+        if from.is_dir() {
+            let mut stack = vec![from.to_path_buf()];
+            while let Some(dir) = stack.pop() {
+                for entry in fs::read_dir(&dir)? {
+                    let path = entry?.path();
+                    if path.is_dir() {
+                        stack.push(path);
+                        continue;
+                    }
+                    let relative = path.strip_prefix(from)?;
+                    match Key::from_path(relative) {
+                        Some(key) => self.import_one(key, fs::read(&path)?, force, &mut summary)?,
+                        None => summary.skipped.push(relative.to_path_buf()),
+                    }
+                }
+            }
+        } else if is_tarball(from) {
+            let file = File::open(from).with_context(|| format!("opening {}", from.display()))?;
+            let reader: Box<dyn Read> = if is_gzip(from) {
+                Box::new(GzDecoder::new(file))
+            } else {
+                Box::new(file)
+            };
+            let mut archive = tar::Archive::new(reader);
+            for entry in archive.entries()? {
+                let mut entry = entry?;
+                if !entry.header().entry_type().is_file() {
+                    continue;
+                }
+                let path = entry.path()?.into_owned();
+                match Key::from_path(&path) {
+                    Some(key) => {
+                        let mut content = Vec::new();
+                        entry.read_to_end(&mut content)?;
+                        self.import_one(key, content, force, &mut summary)?;
+                    }
+                    None => summary.skipped.push(path),
+                }
+            }
+        } else {
+            bail!(
+                "{} is neither a directory nor a .tar/.tar.gz",
+                from.display()
+            );
+        }
+        Ok(summary)
+    }
+    /// exports into a directory or, if `to` ends with .tar/.tar.gz, a tarball
+    pub fn export(&self, to: &Path, year: Option<u16>) -> Result<usize> {
+        let keys: Vec<Key> = self
+            .entries()?
+            .into_iter()
+            .filter(|k| year.is_none_or(|y| k.year == y))
+            .collect();
+        if is_tarball(to) {
+            let file = File::create(to).with_context(|| format!("creating {}", to.display()))?;
+            let writer: Box<dyn Write> = if is_gzip(to) {
+                Box::new(GzEncoder::new(file, Compression::default()))
+            } else {
+                Box::new(file)
+            };
+            let mut builder = tar::Builder::new(writer);
+            for key in keys.iter() {
+                builder.append_path_with_name(self.path(key), key.relative_path())?;
+            }
+            builder.into_inner()?.flush()?;
+        } else {
+            let target = Store::new(to);
+            for key in keys.iter() {
+                target.write(key, &fs::read(self.path(key))?)?;
+            }
+        }
+        Ok(keys.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(year: u16, day: u8, variant: &str) -> Key {
+        Key {
+            year,
+            day,
+            variant: variant.to_owned(),
+        }
+    }
+
+    #[test]
+    fn keys_from_paths() {
+        assert_eq!(
+            Key::from_path(Path::new("2023/8/test2")),
+            Some(key(2023, 8, "test2"))
+        );
+        assert_eq!(
+            Key::from_path(Path::new("inputs/2024/14/input")),
+            Some(key(2024, 14, "input"))
+        );
+        assert_eq!(Key::from_path(Path::new("2024/26/input")), None);
+        assert_eq!(Key::from_path(Path::new("2024/14/in.txt")), None);
+        assert_eq!(key(2024, 1, "test").to_string(), "2024/01/test");
+    }
+
+    #[test]
+    fn export_import_round_trip() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let store = Store::new(dir.path().join("store"));
+        store.write(&key(2023, 1, "input"), b"1abc2")?;
+        store.write(&key(2024, 14, "test"), b"p=0,4 v=3,-3")?;
+
+        for archive in ["out.tar.gz", "out.tar", "out"] {
+            let archive = dir.path().join(archive);
+            assert_eq!(store.export(&archive, None)?, 2);
+            let other = Store::new(dir.path().join(format!("{}-import", archive.display())));
+            let summary = other.import(&archive, false)?;
+            assert_eq!(summary.added, 2);
+            assert_eq!(other.entries()?, store.entries()?);
+            let test = fs::read_to_string(other.path(&key(2024, 14, "test")))?;
+            assert_eq!(test, "p=0,4 v=3,-3");
+        }
+
+        let changed = dir.path().join("changed");
+        Store::new(&changed).write(&key(2023, 1, "input"), b"other")?;
+        let summary = store.import(&changed, false)?;
+        assert_eq!(summary.conflicts, vec![key(2023, 1, "input")]);
+        assert_eq!(fs::read(store.path(&key(2023, 1, "input")))?, b"1abc2");
+        assert_eq!(store.import(&changed, true)?.replaced, 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use crate::scaffold::{Layout, Scaffold};
+use anyhow::{Context, Result};
+use std::{
+    path::{self, PathBuf},
+    process::{self, Command},
+};
+
+/// A day of a year's project, built and run with cargo.
+pub struct Target {
+    pub project: PathBuf,
+    pub layout: Layout,
+    pub year: u16,
+    pub day: u8,
+}
+
+impl Target {
+    pub fn locate(scaffold: &Scaffold, year: u16, day: u8) -> Result<Target> {
+        let (project, layout) = scaffold.project(year)?;
+        Ok(Target {
+            project,
+            layout,
+            year,
+            day,
+        })
+    }
+    pub fn cargo(&self, command: &str) -> Command {
+        let mut cargo = Command::new("cargo");
+        // cargo run for this tool sets the toolchain of the tool, the projects pick their own
+        cargo
+            .current_dir(&self.project)
+            .env_remove("RUSTUP_TOOLCHAIN");
+        if self.layout == Layout::Modules {
+            cargo.arg("+nightly");
+        }
+        cargo.args([command, "--release", "--offline"]);
+        cargo
+    }
+    /// the day's runner with the runner `options`, which the 2022/2023 runners take before `day`
+    pub fn runner(&self, options: &[String]) -> Command {
+        let mut cargo = self.cargo("run");
+        cargo.arg("-q");
+        match self.layout {
+            Layout::Workspace => cargo.args(["-p", &format!("day{:02}", self.day), "--"]),
+            Layout::Modules => cargo.arg("--"),
+        };
+        cargo.args(options);
+        if self.layout == Layout::Modules {
+            cargo.args(["day", &self.day.to_string()]);
+        }
+        cargo
+    }
+    /// runs the runner on the terminal's stdin and stdout, exits with its code if it fails
+    pub fn run(&self, options: &[String]) -> Result<()> {
+        let status = self.runner(options).status().context("running cargo run")?;
+        if !status.success() {
+            process::exit(status.code().unwrap_or(1));
+        }
+        Ok(())
+    }
+}
+
+/// runner options printing a random input instead of solving one
+pub fn generate_options(size: usize, seed: u64) -> Vec<String> {
+    vec![
+        "--generate".to_owned(),
+        size.to_string(),
+        "--seed".to_owned(),
+        seed.to_string(),
+    ]
+}
+
+/// runner options for `input`, `-` is stdin and paths are taken relative to where the tool
+/// runs instead of the project the runner starts in
+pub fn input_options(input: Option<&str>) -> Result<Vec<String>> {
+    let input = match input {
+        None => return Ok(Vec::new()),
+        Some("-") => "-".to_owned(),
+        Some(path) => path::absolute(path)?.display().to_string(),
+    };
+    Ok(vec!["--input".to_owned(), input])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn args(command: &Command) -> Vec<String> {
+        command
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn runner_options_go_before_the_day() -> Result<()> {
+        let mut target = Target {
+            project: PathBuf::from("2024/rust"),
+            layout: Layout::Workspace,
+            year: 2024,
+            day: 16,
+        };
+        assert_eq!(
+            args(&target.runner(&generate_options(1000, 7))).join(" "),
+            "run --release --offline -q -p day16 -- --generate 1000 --seed 7"
+        );
+        target.layout = Layout::Modules;
+        target.day = 22;
+        assert_eq!(
+            args(&target.runner(&input_options(Some("-"))?)).join(" "),
+            "+nightly run --release --offline -q -- --input - day 22"
+        );
+        let options = input_options(Some("big.txt"))?;
+        assert!(Path::new(&options[1]).is_absolute());
+        assert!(input_options(None)?.is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,319 @@
+use crate::scaffold::{Layout, Scaffold};
+use crate::store::{Key, Store};
+use crate::target::Target;
+use anyhow::{Context, Result};
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+    process::Output,
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+
+/// how long to wait for more events after the first, editors write a file in several steps
+const SETTLE: Duration = Duration::from_millis(300);
+
+/// what `cargo test` said about the day's tests
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Tests {
+    passed: usize,
+    failed: Vec<String>,
+    /// first compiler error if nothing could be run
+    build_error: Option<String>,
+}
+
+impl Tests {
+    fn parse(output: &Output) -> Tests {
+        Tests::from_output(
+            output.status.success(),
+            &String::from_utf8_lossy(&output.stdout),
+            &String::from_utf8_lossy(&output.stderr),
+        )
+    }
+    fn from_output(success: bool, stdout: &str, stderr: &str) -> Tests {
+        let mut tests = Tests::default();
+        for line in stdout.lines() {
+            let Some(test) = line.strip_prefix("test ") else {
+                continue;
+            };
+            if test.ends_with(" ... ok") {
+                tests.passed += 1;
+            } else if let Some(name) = test.strip_suffix(" ... FAILED") {
+                tests.failed.push(name.to_owned());
+            }
+        }
+        if !success && !stdout.contains("test result:") {
+            let mut lines = stderr.lines().skip_while(|l| !l.starts_with("error"));
+            let error = match (lines.next(), lines.next()) {
+                (Some(error), Some(at)) if at.trim_start().starts_with("-->") => {
+                    format!("{} {}", error, at.trim())
+                }
+                (Some(error), _) => error.to_owned(),
+                (None, _) => String::from("cargo test failed without an error"),
+            };
+            tests.build_error = Some(error);
+        }
+        tests
+    }
+}
+
+impl std::fmt::Display for Tests {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(error) = &self.build_error {
+            return write!(f, "build failed: {}", error);
+        }
+        write!(f, "{} passed", self.passed)?;
+        if !self.failed.is_empty() {
+            write!(
+                f,
+                ", {} failed: {}",
+                self.failed.len(),
+                self.failed.join(" ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// answer per part from `--format json` runner output, `STATUS: error` for failed parts
+fn parse_answers(stdout: &str) -> BTreeMap<u8, String> {
+    stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|result| {
+            let part = result["part"].as_u64()? as u8;
+            let shown = match (&result["answer"], result["ok"].as_bool()) {
+                (serde_json::Value::String(answer), Some(true)) => answer.clone(),
+                (serde_json::Value::String(answer), _) => format!(
+                    "{} WRONG, expected {}",
+                    answer,
+                    result["expected"].as_str().unwrap_or("?")
+                ),
+                _ => format!(
+                    "{}: {}",
+                    result["status"].as_str().unwrap_or("error").to_uppercase(),
+                    result["error"].as_str().unwrap_or("no answer")
+                ),
+            };
+            Some((part, shown))
+        })
+        .collect()
+}
+
+/// results of one round of tests and answers
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Snapshot {
+    tests: Tests,
+    answers: BTreeMap<u8, String>,
+}
+
+/// compact comparison with the previous round, one line for the tests and one per part
+fn compare(previous: Option<&Snapshot>, current: &Snapshot) -> Vec<String> {
+    let mut lines = Vec::new();
+    let tests = match previous.map(|p| &p.tests) {
+        Some(before) if *before != current.tests => {
+            format!("tests  {}  (was {})", current.tests, before)
+        }
+        _ => format!("tests  {}", current.tests),
+    };
+    lines.push(tests);
+    let parts: BTreeSet<u8> = current
+        .answers
+        .keys()
+        .chain(previous.into_iter().flat_map(|p| p.answers.keys()))
+        .copied()
+        .collect();
+    for part in parts {
+        let before = previous.and_then(|p| p.answers.get(&part));
+        let line = match (before, current.answers.get(&part)) {
+            (None, Some(now)) => now.clone(),
+            (Some(before), Some(now)) if before == now => format!("{}  (unchanged)", now),
+            (Some(before), Some(now)) => format!("{}  (was {})", now, before),
+            (Some(before), None) => format!("no answer  (was {})", before),
+            (None, None) => continue,
+        };
+        lines.push(format!("part{}  {}", part, line));
+    }
+    lines
+}
+
+impl Target {
+    /// the day's sources, the shared code of the year and of all years (`tools/runner`)
+    /// and the day's folder in the store
+    fn watched(&self, store: &Store) -> Vec<PathBuf> {
+        let key = Key {
+            year: self.year,
+            day: self.day,
+            variant: "input".to_owned(),
+        };
+        let inputs = store.path(&key).parent().unwrap().to_owned();
+        let sources = match self.layout {
+            Layout::Workspace => [
+                self.project.join(format!("day{:02}", self.day)),
+                self.project.join("common/src"),
+            ],
+            Layout::Modules => [
+                self.project.join(format!("src/day{:02}", self.day)),
+                self.project.join("src/common"),
+            ],
+        };
+        let runner = Path::new(env!("CARGO_MANIFEST_DIR")).join("../runner/src");
+        sources.into_iter().chain([runner, inputs]).collect()
+    }
+    fn tests(&self) -> Result<Tests> {
+        let mut cargo = self.cargo("test");
+        match self.layout {
+            Layout::Workspace => cargo.args(["-p", &format!("day{:02}", self.day)]),
+            Layout::Modules => cargo.arg(format!("day{:02}::", self.day)),
+        };
+        Ok(Tests::parse(&cargo.output().context("running cargo test")?))
+    }
+    /// the real input from the store, answers are checked against `answers.txt` where there is one
+    fn answers(&self) -> Result<BTreeMap<u8, String>> {
+        let options = ["--format", "json", "--variant", "input"].map(String::from);
+        let output = self
+            .runner(&options)
+            .output()
+            .context("running cargo run")?;
+        Ok(parse_answers(&String::from_utf8_lossy(&output.stdout)))
+    }
+    fn snapshot(&self) -> Result<Snapshot> {
+        let tests = self.tests()?;
+        let answers = match tests.build_error {
+            Some(_) => BTreeMap::new(),
+            None => self.answers()?,
+        };
+        Ok(Snapshot { tests, answers })
+    }
+}
+
+/// Re-runs the tests and the real input of a day whenever its sources or inputs change,
+/// until interrupted.
+pub fn watch(year: u16, day: u8, store: &Store) -> Result<()> {
+    let scaffold = Scaffold::locate();
+    let target = Target::locate(&scaffold, year, day)?;
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    let watched: Vec<PathBuf> = target
+        .watched(store)
+        .into_iter()
+        .filter_map(|p| fs::canonicalize(p).ok())
+        .collect();
+    for path in watched.iter() {
+        watcher
+            .watch(path, RecursiveMode::Recursive)
+            .with_context(|| format!("watching {}", path.display()))?;
+        println!("watching {}", relative(path, scaffold.repo()));
+    }
+
+    let mut previous: Option<Snapshot> = None;
+    let mut changed = BTreeSet::new();
+    loop {
+        let start = Instant::now();
+        let snapshot = target.snapshot()?;
+        let trigger = match changed.len() {
+            0 => String::from("first run"),
+            _ => changed
+                .iter()
+                .map(|p: &PathBuf| relative(p, scaffold.repo()))
+                .collect::<Vec<_>>()
+                .join(" "),
+        };
+        println!(
+            "== {} day {:02} after {} ({:.1?})",
+            year,
+            day,
+            trigger,
+            start.elapsed()
+        );
+        for line in compare(previous.as_ref(), &snapshot) {
+            println!("   {}", line);
+        }
+        previous = Some(snapshot);
+
+        changed.clear();
+        while changed.is_empty() {
+            let event = rx.recv().context("watcher stopped")??;
+            changed.extend(relevant(event));
+            while let Ok(event) = rx.recv_timeout(SETTLE) {
+                changed.extend(relevant(event?));
+            }
+        }
+    }
+}
+
+/// changed files, reads and editor temporaries don't count
+fn relevant(event: notify::Event) -> Vec<PathBuf> {
+    if matches!(event.kind, EventKind::Access(_)) {
+        return Vec::new();
+    }
+    event
+        .paths
+        .into_iter()
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| !n.starts_with('.') && !n.ends_with('~') && !n.ends_with(".swp"))
+        })
+        .collect()
+}
+
+fn relative(path: &Path, repo: &Path) -> String {
+    path.strip_prefix(repo)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_and_answers_are_compared() {
+        let failing = Tests::from_output(
+            false,
+            "running 3 tests\ntest tests::one ... ok\ntest tests::two ... FAILED\ntest tests::bench ... ok\n\ntest result: FAILED. 2 passed; 1 failed\n",
+            "",
+        );
+        assert_eq!(failing.passed, 2);
+        assert_eq!(failing.failed, vec!["tests::two"]);
+        let broken = Tests::from_output(
+            false,
+            "",
+            "   Compiling day17\nerror[E0308]: mismatched types\n  --> day17/src/lib.rs:4:5\n",
+        );
+        assert_eq!(
+            broken.to_string(),
+            "build failed: error[E0308]: mismatched types --> day17/src/lib.rs:4:5"
+        );
+
+        let before = Snapshot {
+            tests: failing,
+            answers: parse_answers(
+                "{\"part\":1,\"answer\":\"11\",\"ok\":true}\n{\"part\":2,\"answer\":\"30\",\"ok\":false,\"expected\":\"31\"}\n",
+            ),
+        };
+        assert_eq!(before.answers[&2], "30 WRONG, expected 31");
+        let after = Snapshot {
+            tests: Tests {
+                passed: 3,
+                ..Tests::default()
+            },
+            answers: parse_answers(
+                "{\"part\":1,\"answer\":\"11\",\"ok\":true}\n{\"part\":2,\"answer\":null,\"ok\":false,\"status\":\"panic\",\"error\":\"boom\"}\n",
+            ),
+        };
+        assert_eq!(
+            compare(Some(&before), &after),
+            vec![
+                "tests  3 passed  (was 2 passed, 1 failed: tests::two)",
+                "part1  11  (unchanged)",
+                "part2  PANIC: boom  (was 30 WRONG, expected 31)",
+            ]
+        );
+        assert_eq!(compare(None, &after)[1], "part1  11");
+    }
+}
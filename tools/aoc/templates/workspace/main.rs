@@ -0,0 +1,6 @@
+use anyhow::Result;
+use day{{DD}}::Day{{DD}};
+
+pub fn main() -> Result<()> {
+    common::run::<Day{{DD}}>()
+}
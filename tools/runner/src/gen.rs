@@ -0,0 +1,75 @@
+/// splitmix64, tiny and seedable, the same seed always gives the same input
+#[derive(Debug, Clone)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed)
+    }
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+    /// uniform in `0..n`
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+    /// uniform in `lo..=hi`
+    pub fn between(&mut self, lo: i128, hi: i128) -> i128 {
+        lo + (self.next_u64() % (hi - lo + 1) as u64) as i128
+    }
+    /// true with a probability of `percent`
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.next_u64() % 100 < percent
+    }
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// Random puzzle inputs of a day for stress and scaling tests, next to the day it feeds.
+///
+/// The runners print one with `--generate <size> [--seed <n>]`, what `size` counts
+/// depends on the day (bricks, machines, side of a maze, ...).
+pub trait InputGenerator {
+    fn generate(&self, size: usize, rng: &mut Rng) -> String;
+}
+
+/// the input for `size` and `seed`, reproducible across runs
+pub fn generate(generator: &impl InputGenerator, size: usize, seed: u64) -> String {
+    generator.generate(size, &mut Rng::new(seed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Lines;
+
+    impl InputGenerator for Lines {
+        fn generate(&self, size: usize, rng: &mut Rng) -> String {
+            (0..size)
+                .map(|_| format!("{}\n", rng.between(-9, 9)))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn seeds_reproduce_inputs() {
+        assert_eq!(generate(&Lines, 50, 7), generate(&Lines, 50, 7));
+        assert_ne!(generate(&Lines, 50, 7), generate(&Lines, 50, 8));
+        assert_eq!(generate(&Lines, 50, 7).lines().count(), 50);
+
+        let mut rng = Rng::new(1);
+        let mut items: Vec<usize> = (0..20).collect();
+        rng.shuffle(&mut items);
+        assert_ne!(items, (0..20).collect::<Vec<_>>());
+        items.sort();
+        assert_eq!(items, (0..20).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,76 @@
+use std::{
+    any::Any,
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+/// some solutions recurse deeply, the main thread used to give them 8MB
+const STACK_SIZE: usize = 64 * 1024 * 1024;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Guarded<T> {
+    Done(T),
+    Panicked(String),
+    TimedOut,
+}
+
+/// Runs `f` on its own thread so a panic or an endless loop can't take down the runner.
+/// A timed out thread can't be killed, it is left running in the background.
+pub fn guarded<T, F>(timeout: Option<Duration>, f: F) -> Guarded<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    let handle = thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            let _ = tx.send(f());
+        })
+        .expect("failed to spawn solver thread");
+    let received = match timeout {
+        Some(timeout) => rx.recv_timeout(timeout),
+        None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match received {
+        Ok(value) => {
+            let _ = handle.join();
+            Guarded::Done(value)
+        }
+        Err(RecvTimeoutError::Timeout) => Guarded::TimedOut,
+        Err(RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(payload) => Guarded::Panicked(panic_message(payload)),
+            Ok(()) => Guarded::Panicked(String::from("solver thread ended without a result")),
+        },
+    }
+}
+
+pub fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(msg) => *msg,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(msg) => msg.to_string(),
+            Err(_) => String::from("unknown panic payload"),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panics_and_timeouts_are_caught() {
+        assert_eq!(guarded(None, || 42), Guarded::Done(42));
+        let panicked = guarded(None, || -> i32 { panic!("can't get given char") });
+        assert_eq!(
+            panicked,
+            Guarded::Panicked("can't get given char".to_owned())
+        );
+        let endless = guarded(Some(Duration::from_millis(50)), || loop {
+            thread::sleep(Duration::from_millis(10));
+        });
+        assert_eq!(endless, Guarded::<()>::TimedOut);
+    }
+}
@@ -0,0 +1,137 @@
+use anyhow::{Context, Result};
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// `path` without `..` and links where it exists
+pub fn canonical(path: PathBuf) -> PathBuf {
+    fs::canonicalize(&path).unwrap_or(path)
+}
+
+/// Root of the git-ignored input store, `AOC_INPUTS` overrides the `inputs` folder
+/// at the top of the repository.
+pub fn store_dir() -> PathBuf {
+    match env::var_os("AOC_INPUTS") {
+        Some(dir) => PathBuf::from(dir),
+        None => canonical(Path::new(env!("CARGO_MANIFEST_DIR")).join("../../inputs")),
+    }
+}
+
+/// `inputs/<year>/<day>/<variant>`, variants are `input`, `test`, `test2`, ...
+pub fn path(year: u16, day: u8, variant: &str) -> PathBuf {
+    store_dir()
+        .join(year.to_string())
+        .join(format!("{:02}", day))
+        .join(variant)
+}
+
+/// A missing entry is an error that names the `tools/aoc` command which adds it,
+/// a fresh clone has no inputs at all.
+pub fn read(year: u16, day: u8, variant: &str) -> Result<String> {
+    let path = path(year, day, variant);
+    let content = match fs::read_to_string(&path) {
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            let command = match variant {
+                "input" => format!(
+                    "`cargo run -- import <dir>` or `cargo run -- fetch {} {}`",
+                    year, day
+                ),
+                _ => "`cargo run -- import <dir>`".to_owned(),
+            };
+            return Err(err).with_context(|| {
+                format!(
+                    "{} is not in the input store, add it with {} in tools/aoc",
+                    path.display(),
+                    command
+                )
+            });
+        }
+        content => content.with_context(|| format!("reading {}", path.display()))?,
+    };
+    Ok(content.replace("\r\n", "\n"))
+}
+
+/// The input of a `local_file!` in the tests, `day` is the name of the day's module or crate
+/// (e.g. `day03`). Panics with the whole error, so a missing input says how to add it.
+pub fn read_for_test(year: u16, day: &str, variant: &str) -> String {
+    let day = day
+        .trim_start_matches("day")
+        .parse()
+        .unwrap_or_else(|_| panic!("{} is not a day", day));
+    read(year, day, variant).unwrap_or_else(|err| panic!("{:#}", err))
+}
+
+/// Where a run takes its puzzle input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    /// `--input -`
+    Stdin,
+    /// `--input <path>`
+    File(PathBuf),
+    /// `--variant <name>`, `input` unless told otherwise
+    Store(String),
+}
+
+impl Default for Source {
+    fn default() -> Self {
+        Source::Store("input".to_owned())
+    }
+}
+
+impl Source {
+    /// `-` is stdin, relative paths are resolved against the year's `project_dir`
+    /// and not the working directory
+    pub fn from_arg(arg: &str, project_dir: &Path) -> Source {
+        match Path::new(arg) {
+            _ if arg == "-" => Source::Stdin,
+            path if path.is_absolute() => Source::File(path.to_owned()),
+            path => Source::File(project_dir.join(path)),
+        }
+    }
+    /// the file that is read, reported with the results
+    pub fn origin(&self, year: u16, day: u8) -> String {
+        match self {
+            Source::Stdin => "stdin".to_owned(),
+            Source::File(path) => path.display().to_string(),
+            Source::Store(variant) => path(year, day, variant).display().to_string(),
+        }
+    }
+    pub fn read(&self, year: u16, day: u8) -> Result<String> {
+        let content = match self {
+            Source::Stdin => io::read_to_string(io::stdin()).context("reading stdin")?,
+            Source::File(path) => {
+                fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?
+            }
+            Source::Store(variant) => return read(year, day, variant),
+        };
+        Ok(content.replace("\r\n", "\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_inputs_name_the_import() {
+        // no year 1 in any store
+        let err = format!("{:#}", read(1, 1, "input").unwrap_err());
+        assert!(
+            err.contains("01/input is not in the input store"),
+            "{}",
+            err
+        );
+        assert!(
+            err.contains("`cargo run -- import <dir>` or `cargo run -- fetch 1 1`"),
+            "{}",
+            err
+        );
+        let err = format!("{:#}", read(1, 1, "test2").unwrap_err());
+        assert!(!err.contains("fetch"), "{}", err);
+        assert_eq!(
+            Source::from_arg("day01/example.txt", Path::new("/aoc/2024/rust")),
+            Source::File(PathBuf::from("/aoc/2024/rust/day01/example.txt"))
+        );
+    }
+}
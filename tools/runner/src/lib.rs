@@ -0,0 +1,10 @@
+//! What every year's runner has in common: reading inputs from the store, logging to stderr,
+//! so stdout only ever contains answers, running each part guarded against panics and timeouts,
+//! and reporting the answers in the format asked for. Days with an input generator share its
+//! seedable `Rng`.
+
+pub mod gen;
+pub mod guard;
+pub mod inputs;
+pub mod log;
+pub mod report;
@@ -0,0 +1,130 @@
+use anyhow::{bail, Context, Result};
+use std::{
+    fmt,
+    fs::File,
+    io::Write,
+    path::Path,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Mutex,
+    },
+};
+
+/// Diagnostic output goes here instead of stdout, so stdout only ever contains answers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off = 0,
+    Debug = 1,
+    Trace = 2,
+}
+
+impl Level {
+    /// -v -> Debug, -vv -> Trace
+    pub fn from_verbosity(verbose: u64) -> Level {
+        match verbose {
+            0 => Level::Off,
+            1 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+static FILTER: Mutex<Vec<String>> = Mutex::new(Vec::new());
+static SINK: Mutex<Option<File>> = Mutex::new(None);
+
+/// `filter` is a comma separated list of crate or module names (e.g. `day09,day12`),
+/// without a file the output goes to stderr
+pub fn init(level: Level, filter: Option<&str>, file: Option<&Path>) -> Result<()> {
+    *FILTER.lock().unwrap() = filter
+        .map(|f| f.split(',').map(String::from).collect())
+        .unwrap_or_default();
+    *SINK.lock().unwrap() = file.map(File::create).transpose()?;
+    LEVEL.store(level as u8, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Takes `-v`, `-vv`, `--log-day <days>` and `--log-file <path>` out of the
+/// command line arguments and initializes logging with them.
+/// The remaining arguments (without the program name) are returned.
+pub fn init_from_args(args: impl IntoIterator<Item = String>) -> Result<Vec<String>> {
+    let mut verbose = 0;
+    let mut filter = None;
+    let mut file = None;
+    let mut rest = Vec::new();
+    let mut args = args.into_iter().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--log-day" => filter = Some(args.next().context("--log-day needs a value")?),
+            "--log-file" => file = Some(args.next().context("--log-file needs a value")?),
+            "--verbose" => verbose += 1,
+            v if v.len() > 1 && v.starts_with('-') && v[1..].chars().all(|c| c == 'v') => {
+                verbose += v.len() as u64 - 1
+            }
+            v if v.starts_with("--log-") => bail!("unknown logging option {}", v),
+            _ => rest.push(arg),
+        }
+    }
+    init(
+        Level::from_verbosity(verbose),
+        filter.as_deref(),
+        file.as_deref().map(Path::new),
+    )?;
+    Ok(rest)
+}
+
+/// `module` is a `module_path!()`, a filter entry has to be one of its segments,
+/// so `day1` does not enable `day12`
+pub fn enabled(level: Level, module: &str) -> bool {
+    if LEVEL.load(Ordering::Relaxed) < level as u8 {
+        return false;
+    }
+    let filter = FILTER.lock().unwrap();
+    filter.is_empty() || filter.iter().any(|f| module.split("::").any(|s| s == f))
+}
+
+pub fn write(level: Level, module: &str, args: fmt::Arguments) {
+    let line = format!("[{:?} {}] {}\n", level, module, args);
+    match SINK.lock().unwrap().as_mut() {
+        Some(file) => file.write_all(line.as_bytes()).unwrap(),
+        None => eprint!("{}", line),
+    }
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Debug, module_path!()) {
+            $crate::log::write($crate::log::Level::Debug, module_path!(), format_args!($($arg)*));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Trace, module_path!()) {
+            $crate::log::write($crate::log::Level::Trace, module_path!(), format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logging_args_are_stripped() -> Result<()> {
+        let args = ["day09", "-vv", "--log-day", "day09", "out.txt"].map(String::from);
+        assert_eq!(init_from_args(args)?, vec!["out.txt"]);
+        assert!(enabled(Level::Trace, "day09"));
+        assert!(!enabled(Level::Debug, "day12"));
+        init(Level::Debug, Some("day1,day24"), None)?;
+        assert!(enabled(Level::Debug, "rust::day1"));
+        assert!(enabled(Level::Debug, "day24::netlist"));
+        assert!(!enabled(Level::Debug, "rust::day12"));
+        init(Level::Off, None, None)?;
+        assert!(!enabled(Level::Debug, "day09"));
+        Ok(())
+    }
+}
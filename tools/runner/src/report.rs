@@ -0,0 +1,405 @@
+use crate::inputs::Source;
+use anyhow::{bail, Result};
+use serde::Serialize;
+use std::{fmt, path::Path, str::FromStr, time::Duration};
+
+/// How results are written to stdout, `Text` is the classic `part1: <answer>`
+/// (`day01 part01: <answer>` for runners of several days).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+    Table,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s {
+            "text" => Format::Text,
+            "json" => Format::Json,
+            "csv" => Format::Csv,
+            "table" => Format::Table,
+            _ => bail!("unknown format {}, expected text|json|csv|table", s),
+        })
+    }
+}
+
+/// Runner options of the day binaries (2024 on), the older runners parse theirs with structopt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    pub format: Format,
+    /// wall-clock limit per part, `None` waits forever
+    pub timeout: Option<Duration>,
+    /// `None` takes the `input` of the store, stdin is only read with `--input -`
+    pub input: Option<Source>,
+    /// `--generate <size>` prints a random input of that size instead of solving one
+    pub generate: Option<usize>,
+    pub seed: u64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            format: Format::default(),
+            timeout: Some(Duration::from_secs(60)),
+            input: None,
+            generate: None,
+            seed: 0,
+        }
+    }
+}
+
+impl Options {
+    /// Takes `--format <text|json|csv|table>`, `--timeout <seconds>` (0 disables it),
+    /// `--input <path|->`, `--variant <name>`, `--generate <size>` and `--seed <n>`
+    /// out of the arguments, relative `--input` paths start at `project_dir`.
+    pub fn from_args(args: Vec<String>, project_dir: &Path) -> Result<(Options, Vec<String>)> {
+        let mut options = Options::default();
+        let mut rest = Vec::new();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" | "--timeout" | "--input" | "--variant" | "--generate" | "--seed" => {
+                    let Some(value) = args.next() else {
+                        bail!("{} needs a value", arg);
+                    };
+                    match arg.as_str() {
+                        "--format" => options.format = value.parse()?,
+                        "--timeout" => {
+                            let secs: f64 = value.parse()?;
+                            options.timeout = (secs > 0.0).then(|| Duration::from_secs_f64(secs));
+                        }
+                        "--generate" => options.generate = Some(value.parse()?),
+                        "--seed" => options.seed = value.parse()?,
+                        _ if options.input.is_some() => bail!("only one --input or --variant"),
+                        "--input" => options.input = Some(Source::from_arg(&value, project_dir)),
+                        _ => options.input = Some(Source::Store(value)),
+                    }
+                }
+                _ => rest.push(arg),
+            }
+        }
+        Ok((options, rest))
+    }
+    /// the explicit input, otherwise the store's `input` even if something is piped
+    pub fn source(&self) -> Source {
+        self.input.clone().unwrap_or_default()
+    }
+}
+
+/// How a part ended, `ok` is a shorthand for `status == Status::Ok`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Ok,
+    Wrong,
+    Error,
+    Panic,
+    Timeout,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::Wrong => write!(f, "WRONG"),
+            Status::Error => write!(f, "ERROR"),
+            Status::Panic => write!(f, "PANIC"),
+            Status::Timeout => write!(f, "TIMEOUT"),
+        }
+    }
+}
+
+/// Outcome of solving one part, this is what the json format emits per line.
+#[derive(Debug, Clone, Serialize)]
+pub struct PartResult {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub ok: bool,
+    pub status: Status,
+    pub expected: Option<String>,
+    pub parse_ns: Option<u128>,
+    pub solve_ns: Option<u128>,
+    /// the whole anyhow chain, outermost context first
+    pub error: Option<String>,
+    /// the file the input was read from, `stdin` if it was piped
+    pub input: Option<String>,
+}
+
+impl PartResult {
+    pub fn new(year: u16, day: u8, part: u8) -> PartResult {
+        PartResult {
+            year,
+            day,
+            part,
+            answer: None,
+            ok: false,
+            status: Status::Error,
+            expected: None,
+            parse_ns: None,
+            solve_ns: None,
+            error: None,
+            input: None,
+        }
+    }
+    /// fills in answer or error, `ok` needs an answer that matches `expected` if that is known
+    pub fn with_outcome<T: fmt::Display>(mut self, outcome: &Result<T>) -> PartResult {
+        match outcome {
+            Ok(answer) => self.answer = Some(answer.to_string()),
+            Err(err) => self.error = Some(format!("{:#}", err)),
+        }
+        self.check();
+        self
+    }
+    pub fn with_input(mut self, input: String) -> PartResult {
+        self.input = Some(input);
+        self
+    }
+    pub fn with_expected(mut self, expected: Option<String>) -> PartResult {
+        self.expected = expected;
+        if self.status != Status::Panic && self.status != Status::Timeout {
+            self.check();
+        }
+        self
+    }
+    pub fn with_panic(mut self, msg: &str) -> PartResult {
+        self.error = Some(msg.to_owned());
+        self.status = Status::Panic;
+        self.ok = false;
+        self
+    }
+    pub fn with_timeout(mut self, limit: Duration) -> PartResult {
+        self.error = Some(format!("no answer within {:?}", limit));
+        self.status = Status::Timeout;
+        self.ok = false;
+        self
+    }
+    /// why this part is not ok, if it isn't
+    pub fn failure(&self) -> Option<String> {
+        if self.ok {
+            return None;
+        }
+        Some(match (&self.answer, &self.expected, &self.error) {
+            (Some(answer), Some(expected), _) => format!("got {}, expected {}", answer, expected),
+            (_, _, Some(err)) => err.clone(),
+            _ => String::from("no answer"),
+        })
+    }
+    fn check(&mut self) {
+        self.status = match (&self.answer, &self.expected) {
+            (Some(answer), Some(expected)) if answer != expected => Status::Wrong,
+            (Some(_), _) => Status::Ok,
+            (None, _) => Status::Error,
+        };
+        self.ok = self.status == Status::Ok;
+    }
+}
+
+fn format_ns(ns: Option<u128>) -> String {
+    ns.map(|ns| format!("{:?}", Duration::from_nanos(ns as u64)))
+        .unwrap_or_default()
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+/// Writes results in the requested format, the table is only printed on `finish`
+/// because the column widths depend on all rows. In text the input file goes to stderr
+/// whenever it changes.
+pub struct Reporter {
+    format: Format,
+    /// text lines name the day as well
+    days: bool,
+    rows: Vec<PartResult>,
+}
+
+impl Reporter {
+    /// for the binary of one day, text lines are `part1: <answer>` as `aoc submit` reads them
+    pub fn new(format: Format) -> Reporter {
+        if format == Format::Csv {
+            println!("year,day,part,answer,ok,status,expected,parse_ns,solve_ns,error,input");
+        }
+        Reporter {
+            format,
+            days: false,
+            rows: Vec::new(),
+        }
+    }
+    /// for runners of several days, text lines are `day01 part01: <answer>`
+    pub fn for_days(format: Format) -> Reporter {
+        Reporter {
+            days: true,
+            ..Reporter::new(format)
+        }
+    }
+    fn label(&self, result: &PartResult) -> String {
+        if self.days {
+            format!("day{:02} part{:02}", result.day, result.part)
+        } else {
+            format!("part{}", result.part)
+        }
+    }
+    pub fn report(&mut self, result: PartResult) {
+        if self.format == Format::Text {
+            let last_input = self.rows.last().and_then(|r| r.input.as_ref());
+            if let Some(input) = result.input.as_ref().filter(|&i| Some(i) != last_input) {
+                eprintln!("day{:02} input: {}", result.day, input);
+            }
+        }
+        match self.format {
+            Format::Text => match (&result.answer, result.failure()) {
+                (Some(answer), None) => println!("{}: {}", self.label(&result), answer),
+                (_, failure) => println!(
+                    "{}: {}: {}",
+                    self.label(&result),
+                    result.status,
+                    failure.unwrap_or_default()
+                ),
+            },
+            Format::Json => println!("{}", serde_json::to_string(&result).unwrap()),
+            Format::Csv => {
+                let fields = [
+                    result.year.to_string(),
+                    result.day.to_string(),
+                    result.part.to_string(),
+                    result.answer.clone().unwrap_or_default(),
+                    result.ok.to_string(),
+                    result.status.to_string().to_lowercase(),
+                    result.expected.clone().unwrap_or_default(),
+                    result.parse_ns.map(|n| n.to_string()).unwrap_or_default(),
+                    result.solve_ns.map(|n| n.to_string()).unwrap_or_default(),
+                    result.error.clone().unwrap_or_default(),
+                    result.input.clone().unwrap_or_default(),
+                ];
+                let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+                println!("{}", fields.join(","));
+            }
+            Format::Table => {}
+        }
+        self.rows.push(result);
+    }
+    /// Prints the table and a summary of all failed parts to stderr,
+    /// returns whether every part was ok.
+    pub fn finish(self) -> bool {
+        if self.format == Format::Table {
+            self.print_table();
+        }
+        let failures: Vec<String> = self
+            .rows
+            .iter()
+            .filter_map(|r| {
+                let failure = r.failure()?;
+                Some(format!("{}: {}: {}", self.label(r), r.status, failure))
+            })
+            .collect();
+        if !failures.is_empty() {
+            eprintln!("{} of {} parts failed:", failures.len(), self.rows.len());
+            for failure in failures.iter() {
+                eprintln!("  {}", failure);
+            }
+        }
+        failures.is_empty()
+    }
+    fn print_table(&self) {
+        let header = [
+            "year", "day", "part", "answer", "status", "parse", "solve", "error",
+        ];
+        let rows: Vec<[String; 8]> = self
+            .rows
+            .iter()
+            .map(|r| {
+                [
+                    r.year.to_string(),
+                    format!("{:02}", r.day),
+                    r.part.to_string(),
+                    r.answer.clone().unwrap_or_default(),
+                    r.status.to_string(),
+                    format_ns(r.parse_ns),
+                    format_ns(r.solve_ns),
+                    r.error.clone().unwrap_or_default(),
+                ]
+            })
+            .collect();
+        let mut widths = header.map(str::len);
+        for row in rows.iter() {
+            for (w, field) in widths.iter_mut().zip(row.iter()) {
+                *w = (*w).max(field.len());
+            }
+        }
+        let line = |fields: &[String]| {
+            let padded: Vec<String> = fields
+                .iter()
+                .zip(widths.iter())
+                .map(|(f, &w)| format!("{:<w$}", f, w = w))
+                .collect();
+            println!("{}", padded.join(" | ").trim_end());
+        };
+        line(&header.map(String::from));
+        println!("{}", widths.map(|w| "-".repeat(w)).join("-+-"));
+        for row in rows.iter() {
+            line(row);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{anyhow, Context};
+    use std::path::PathBuf;
+
+    #[test]
+    fn error_chain_is_kept() {
+        let err: Result<i128> = Err(anyhow!("no S in grid")).context("parsing day 20");
+        let result = PartResult::new(2023, 20, 1).with_outcome(&err);
+        assert!(!result.ok);
+        assert_eq!(
+            result.failure().as_deref(),
+            Some("parsing day 20: no S in grid")
+        );
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains(r#""error":"parsing day 20: no S in grid""#));
+        let wrong = PartResult::new(2023, 20, 1)
+            .with_outcome(&Ok(3))
+            .with_expected(Some("4".to_owned()));
+        assert_eq!(wrong.failure().as_deref(), Some("got 3, expected 4"));
+    }
+
+    #[test]
+    fn options_are_stripped() -> Result<()> {
+        let project = PathBuf::from("/aoc/2024/rust");
+        let args = ["--format", "csv", "x", "--timeout", "0"].map(String::from);
+        let (options, rest) = Options::from_args(args.to_vec(), &project)?;
+        assert_eq!(options.format, Format::Csv);
+        assert_eq!(options.timeout, None);
+        assert_eq!(rest, vec!["x".to_owned()]);
+        // stdin of the tests is not a terminal, it must not be read unless asked for
+        assert_eq!(options.source(), Source::default());
+
+        let args = ["--variant", "test2"].map(String::from);
+        let (options, _) = Options::from_args(args.to_vec(), &project)?;
+        assert_eq!(options.source(), Source::Store("test2".to_owned()));
+        let args = ["--input", "day01/example.txt"].map(String::from);
+        let (options, _) = Options::from_args(args.to_vec(), &project)?;
+        let Some(Source::File(path)) = options.input else {
+            panic!("not a file: {:?}", options.input);
+        };
+        assert_eq!(path, project.join("day01/example.txt"));
+        let args = ["--input", "-", "--variant", "test"].map(String::from);
+        assert!(Options::from_args(args.to_vec(), &project).is_err());
+        let args = ["--generate", "1000", "--seed", "7"].map(String::from);
+        let (options, _) = Options::from_args(args.to_vec(), &project)?;
+        assert_eq!((options.generate, options.seed), (Some(1000), 7));
+        Ok(())
+    }
+}